@@ -0,0 +1,27 @@
+use dioxus::prelude::*;
+use crate::types::{BuildLogLine, LogStream};
+
+/// Terminal-style view of build output with timestamps; stderr in red
+#[component]
+pub fn BuildLogView(lines: Vec<BuildLogLine>) -> Element {
+    rsx! {
+        // column-reverse keeps the view pinned to the newest line
+        div { class: "bg-gray-900 rounded-md p-3 h-80 overflow-y-auto flex flex-col-reverse font-mono text-xs",
+            div {
+                for line in lines.iter() {
+                    div {
+                        class: match line.stream {
+                            LogStream::Stderr => "whitespace-pre-wrap text-red-400",
+                            LogStream::Info => "whitespace-pre-wrap text-blue-300 font-semibold",
+                            LogStream::Stdout => "whitespace-pre-wrap text-gray-100",
+                        },
+                        span { class: "text-gray-500 mr-2",
+                            {line.timestamp.format("%H:%M:%S").to_string()}
+                        }
+                        "{line.text}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod build_log;
 pub mod project_card;
 
+pub use build_log::BuildLogView;
 pub use project_card::ProjectCard;
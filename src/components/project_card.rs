@@ -38,9 +38,9 @@ pub fn ProjectCard(project: Project, on_delete: Option<EventHandler<String>>) ->
                         span { class: "text-xs bg-blue-100 text-blue-800 px-2 py-1 rounded",
                             "{project.target_paths.len()} paths"
                         }
-                        if !project.selected_build_commands.is_empty() {
+                        if !project.build_steps.is_empty() {
                             span { class: "text-xs bg-green-100 text-green-800 px-2 py-1 rounded",
-                                "{project.selected_build_commands.len()} commands"
+                                "{project.build_steps.len()} commands"
                             }
                         }
                     }
@@ -1,6 +1,6 @@
 use crate::types::*;
 use serde_json;
-use dioxus::prelude::{Writable, Readable};
+use dioxus::prelude::Writable;
 use sysinfo::{System, Pid};
 
 // Note: Global process tracking system removed as it's not currently used
@@ -18,7 +18,7 @@ pub async fn kill_process_tree(pid: u32) -> Result<(), String> {
     // Add the parent process
     processes_to_kill.push(pid);
     
-    println!("🧹 Killing process tree for PID {}: {} processes", pid, processes_to_kill.len());
+    eprintln!("🧹 Killing process tree for PID {}: {} processes", pid, processes_to_kill.len());
     
     // For script processes, also try to kill by process group - cross-platform
     #[cfg(unix)]
@@ -26,7 +26,7 @@ pub async fn kill_process_tree(pid: u32) -> Result<(), String> {
         if let Some(process) = system.process(Pid::from(pid as usize)) {
             let process_name = process.name();
             if process_name.contains("bash") || process_name.contains("sh") {
-                println!("🔪 Detected bash process, using process group kill");
+                eprintln!("🔪 Detected bash process, using process group kill");
                 // Kill the entire process group
                 let _ = std::process::Command::new("kill")
                     .arg("-TERM")
@@ -50,7 +50,7 @@ pub async fn kill_process_tree(pid: u32) -> Result<(), String> {
         if let Some(process) = system.process(Pid::from(pid as usize)) {
             let process_name = process.name();
             if process_name.contains("cmd") || process_name.contains("conhost") {
-                println!("🔪 Detected Windows command process, using taskkill");
+                eprintln!("🔪 Detected Windows command process, using taskkill");
                 // Kill the entire process tree on Windows
                 let _ = std::process::Command::new("taskkill")
                     .args(["/F", "/T", "/PID", &pid.to_string()])
@@ -71,7 +71,7 @@ pub async fn kill_process_tree(pid: u32) -> Result<(), String> {
         if process_name.contains("npm") || process_name.contains("node") {
             if let Some(parent_pid) = process.parent() {
                 if parent_pid.as_u32() == pid || processes_to_kill.contains(&parent_pid.as_u32()) {
-                    println!("🔪 Killing orphaned {}: {}", process_name, proc_pid.as_u32());
+                    eprintln!("🔪 Killing orphaned {}: {}", process_name, proc_pid.as_u32());
                     kill_single_process(proc_pid.as_u32()).await;
                 }
             }
@@ -102,7 +102,7 @@ async fn kill_single_process(pid: u32) {
         // On Unix systems, use SIGTERM first, then SIGKILL if needed
         use std::process::Command;
         
-        println!("🔪 Terminating process {} (SIGTERM)", pid);
+        eprintln!("🔪 Terminating process {} (SIGTERM)", pid);
         let _ = Command::new("kill")
             .arg("-TERM")
             .arg(pid.to_string())
@@ -112,7 +112,7 @@ async fn kill_single_process(pid: u32) {
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
         
         // Force kill if still running
-        println!("🔪 Force killing process {} (SIGKILL)", pid);
+        eprintln!("🔪 Force killing process {} (SIGKILL)", pid);
         let _ = Command::new("kill")
             .arg("-KILL")
             .arg(pid.to_string())
@@ -124,7 +124,7 @@ async fn kill_single_process(pid: u32) {
         // On Windows, use taskkill
         use std::process::Command;
         
-        println!("🔪 Terminating process {} (Windows taskkill)", pid);
+        eprintln!("🔪 Terminating process {} (Windows taskkill)", pid);
         let _ = Command::new("taskkill")
             .args(["/F", "/PID", &pid.to_string()])
             .output();
@@ -133,34 +133,179 @@ async fn kill_single_process(pid: u32) {
 
 // Note: cleanup_all_processes function removed as global process tracking was removed
 
-// Helper function to find npm binary path - cross-platform
-fn find_npm_path() -> Option<String> {
+// Detect the package manager for a project from package.json and lockfiles
+// The `packageManager` field (corepack) wins over lockfiles; npm is the fallback
+pub fn detect_package_manager(project_path: &str) -> PackageManager {
+    let project_dir = std::path::Path::new(project_path);
+    
+    if let Ok(content) = std::fs::read_to_string(project_dir.join("package.json")) {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+            if let Some(field) = json.get("packageManager").and_then(|v| v.as_str()) {
+                // Format is "<name>@<version>", e.g. "pnpm@8.15.4"
+                let name = field.split('@').next().unwrap_or("");
+                if let Some(pm) = PackageManager::from_name(name) {
+                    return pm;
+                }
+            }
+        }
+    }
+    
+    // Check lockfiles in order of specificity; package-lock.json last since
+    // it is sometimes committed by accident next to another lockfile
+    let lockfile_order = [
+        PackageManager::Pnpm,
+        PackageManager::Yarn,
+        PackageManager::Bun,
+        PackageManager::Npm,
+    ];
+    for pm in lockfile_order {
+        if project_dir.join(pm.lockfile()).exists() {
+            return pm;
+        }
+    }
+    
+    PackageManager::Npm
+}
+
+// Resolve the package manager to use for a project: the user's override or the detected one
+// Workspace packages usually have no lockfile of their own, so detection looks at the root
+pub fn resolve_package_manager(project: &Project) -> PackageManager {
+    project.package_manager.unwrap_or_else(|| match &project.workspace {
+        Some(link) => detect_package_manager(&link.root),
+        None => detect_package_manager(&project.path),
+    })
+}
+
+// Arguments that run one of the project's scripts, e.g. ["run", "build"] or
+// ["--filter", "@scope/ui", "run", "build"] for a workspace-scoped package
+pub fn command_args(project: &Project, pm: PackageManager, script: &str) -> Vec<String> {
+    match &project.workspace {
+        Some(link) if link.scope == BuildScope::Workspace => pm.workspace_run_args(&link.package_name, script),
+        _ => pm.run_args(script),
+    }
+}
+
+// Folder the project's scripts run in
+pub fn command_dir(project: &Project) -> &str {
+    match &project.workspace {
+        Some(link) if link.scope == BuildScope::Workspace => &link.root,
+        _ => &project.path,
+    }
+}
+
+// Full command line of a build step, e.g. "pnpm run build" or "sh -c ./scripts/gen-icons.sh"
+pub fn step_command_line(project: &Project, pm: PackageManager, step: &BuildStep) -> String {
+    let command = step_command(project, pm, step);
+    let program = match &step.kind {
+        StepKind::Script { .. } => pm.binary_name().to_string(),
+        _ => command.program,
+    };
+    join_args(&[vec![program], command.args].concat())
+}
+
+// Quote an argument when split_args would not read it back as is: spaces, quotes,
+// and backslashes (e.g. C:\tools\gen.exe), which it takes as escapes outside quotes
+pub fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"' || c == '\\') {
+        arg.to_string()
+    } else if !arg.contains('\'') {
+        format!("'{}'", arg)
+    } else {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+// Arguments joined back into one line that split_args reads the same way
+pub fn join_args(args: &[String]) -> String {
+    args.iter().map(|arg| quote_arg(arg)).collect::<Vec<_>>().join(" ")
+}
+
+// Split a typed command line into arguments, the way a POSIX shell would for
+// plain words, 'single quoted' and "double quoted" text; nothing is expanded
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("Missing closing ' quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => arg.push(c),
+                            None => return Err("Missing closing \" quote".to_string()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("Missing closing \" quote".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    current.get_or_insert_with(String::new).push(c);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+// Helper function to find a package manager binary path - cross-platform
+fn find_package_manager_path(pm: PackageManager) -> Option<String> {
+    let binary = pm.binary_name();
+    
     #[cfg(windows)]
     {
-        // Windows-specific npm locations
-        let possible_paths = [
-            "C:\\Program Files\\nodejs\\npm.cmd",
-            "C:\\Program Files (x86)\\nodejs\\npm.cmd",
+        let mut possible_paths = vec![
+            format!("C:\\Program Files\\nodejs\\{}.cmd", binary),
+            format!("C:\\Program Files (x86)\\nodejs\\{}.cmd", binary),
         ];
         
-        // Check AppData npm location
+        // Global installs through npm/corepack end up in AppData
         if let Ok(appdata) = std::env::var("APPDATA") {
-            let npm_path = format!("{}\\npm\\npm.cmd", appdata);
-            if std::path::Path::new(&npm_path).exists() {
-                return Some(npm_path);
+            possible_paths.insert(0, format!("{}\\npm\\{}.cmd", appdata, binary));
+        }
+        
+        // pnpm standalone installer and bun have their own locations
+        if let Ok(local_appdata) = std::env::var("LOCALAPPDATA") {
+            if pm == PackageManager::Pnpm {
+                possible_paths.push(format!("{}\\pnpm\\pnpm.exe", local_appdata));
+            }
+        }
+        if let Some(home) = dirs::home_dir() {
+            if pm == PackageManager::Bun {
+                possible_paths.push(format!("{}\\.bun\\bin\\bun.exe", home.display()));
             }
         }
         
-        // Check Program Files locations
         for path in &possible_paths {
             if std::path::Path::new(path).exists() {
                 return Some(path.to_string());
             }
         }
         
-        // Try to find npm using where command (Windows equivalent of which)
+        // Try to find the binary using where command (Windows equivalent of which)
         if let Ok(output) = std::process::Command::new("where")
-            .arg("npm")
+            .arg(binary)
             .output() {
             if output.status.success() {
                 if let Ok(path) = String::from_utf8(output.stdout) {
@@ -175,23 +320,40 @@ fn find_npm_path() -> Option<String> {
     
     #[cfg(unix)]
     {
-        // Unix (macOS/Linux) npm locations
-        let possible_paths = [
-            "/usr/local/bin/npm",
-            "/opt/homebrew/bin/npm",
-            "/usr/bin/npm",
-            "/home/linuxbrew/.linuxbrew/bin/npm", // Linux Homebrew
+        // Unix (macOS/Linux) locations
+        let mut possible_paths = vec![
+            format!("/usr/local/bin/{}", binary),
+            format!("/opt/homebrew/bin/{}", binary),
+            format!("/usr/bin/{}", binary),
+            format!("/home/linuxbrew/.linuxbrew/bin/{}", binary), // Linux Homebrew
         ];
         
+        // pnpm standalone installer and bun install into the home directory
+        if let Some(home) = dirs::home_dir() {
+            match pm {
+                PackageManager::Pnpm => {
+                    possible_paths.push(format!("{}/.local/share/pnpm/pnpm", home.display()));
+                    possible_paths.push(format!("{}/Library/pnpm/pnpm", home.display()));
+                }
+                PackageManager::Bun => {
+                    possible_paths.push(format!("{}/.bun/bin/bun", home.display()));
+                }
+                PackageManager::Yarn => {
+                    possible_paths.push(format!("{}/.yarn/bin/yarn", home.display()));
+                }
+                PackageManager::Npm => {}
+            }
+        }
+        
         for path in &possible_paths {
             if std::path::Path::new(path).exists() {
                 return Some(path.to_string());
             }
         }
         
-        // Try to find npm using which command
+        // Try to find the binary using which command
         if let Ok(output) = std::process::Command::new("which")
-            .arg("npm")
+            .arg(binary)
             .output() {
             if output.status.success() {
                 if let Ok(path) = String::from_utf8(output.stdout) {
@@ -207,92 +369,112 @@ fn find_npm_path() -> Option<String> {
     None
 }
 
-// Helper function to create and execute a script with npm commands - cross-platform
-fn create_build_script(commands: &[String], project_path: &str) -> Result<String, String> {
-    use std::io::Write;
-    
-    // Find npm binary path
-    let npm_path = find_npm_path().unwrap_or_else(|| {
+// Program that runs the package manager, its full path when it can be found
+fn package_manager_program(pm: PackageManager) -> String {
+    find_package_manager_path(pm).unwrap_or_else(|| {
         #[cfg(windows)]
-        { "npm.cmd".to_string() }
+        { format!("{}.cmd", pm.binary_name()) }
         #[cfg(unix)]
-        { "npm".to_string() }
-    });
-    
+        { pm.binary_name().to_string() }
+    })
+}
+
+// PATH for build commands: common Node.js locations first, since apps started from
+// the desktop often get a PATH without them, then the inherited PATH
+fn build_path_var() -> std::ffi::OsString {
     #[cfg(windows)]
-    {
-        // Create Windows batch script
-        let script_path = format!("{}\\build_script.bat", project_path);
-        
-        // Generate batch script content
-        let mut script_content = String::from("@echo off\nsetlocal\n\n");
-        
-        // Add common Node.js paths to PATH for Windows
-        script_content.push_str("set \"PATH=C:\\Program Files\\nodejs;C:\\Program Files (x86)\\nodejs;%APPDATA%\\npm;%PATH%\"\n\n");
-        
-        for command in commands {
-            script_content.push_str(&format!("echo Running: \"{}\" run {}\n", npm_path, command));
-            script_content.push_str(&format!("\"{}\" run {}\n", npm_path, command));
-            script_content.push_str("if errorlevel 1 exit /b 1\n\n");
-        }
-        
-        // Write script to file
-        match std::fs::File::create(&script_path) {
-            Ok(mut file) => {
-                if let Err(e) = file.write_all(script_content.as_bytes()) {
-                    return Err(format!("Failed to write script: {}", e));
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to create script file: {}", e));
-            }
+    let mut dirs: Vec<std::path::PathBuf> = {
+        let mut dirs = vec![
+            std::path::PathBuf::from("C:\\Program Files\\nodejs"),
+            std::path::PathBuf::from("C:\\Program Files (x86)\\nodejs"),
+        ];
+        if let Ok(appdata) = std::env::var("APPDATA") {
+            dirs.push(std::path::Path::new(&appdata).join("npm"));
         }
-        
-        Ok(script_path)
-    }
+        dirs
+    };
     
     #[cfg(unix)]
-    {
-        // Create Unix bash script
-        let script_path = format!("{}/build_script.sh", project_path);
-        
-        // Generate script content with full npm path and proper PATH setup
-        let mut script_content = String::from("#!/bin/bash\nset -e\n\n");
-        
-        // Add common Node.js paths to PATH
-        script_content.push_str("export PATH=\"/usr/local/bin:/opt/homebrew/bin:/usr/bin:/home/linuxbrew/.linuxbrew/bin:$PATH\"\n\n");
-        
-        for command in commands {
-            script_content.push_str(&format!("echo 'Running: {} run {}'\n", npm_path, command));
-            script_content.push_str(&format!("{} run {}\n", npm_path, command));
-        }
-        
-        // Write script to file
-        match std::fs::File::create(&script_path) {
-            Ok(mut file) => {
-                if let Err(e) = file.write_all(script_content.as_bytes()) {
-                    return Err(format!("Failed to write script: {}", e));
-                }
-            }
-            Err(e) => {
-                return Err(format!("Failed to create script file: {}", e));
-            }
-        }
-        
-        // Make script executable
-        use std::os::unix::fs::PermissionsExt;
-        if let Err(e) = std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)) {
-            return Err(format!("Failed to set script permissions: {}", e));
+    let mut dirs: Vec<std::path::PathBuf> = ["/usr/local/bin", "/opt/homebrew/bin", "/usr/bin", "/home/linuxbrew/.linuxbrew/bin"]
+        .iter()
+        .map(std::path::PathBuf::from)
+        .collect();
+    
+    if let Some(path) = std::env::var_os("PATH") {
+        dirs.extend(std::env::split_paths(&path));
+    }
+    std::env::join_paths(dirs).unwrap_or_default()
+}
+
+// Earlier versions ran the commands through a generated build_script.sh/.bat in the
+// project folder and could leave it behind; remove it, but only if it is ours
+fn remove_leftover_build_script(dir: &str) {
+    for name in ["build_script.sh", "build_script.bat"] {
+        let path = std::path::Path::new(dir).join(name);
+        let Ok(content) = std::fs::read_to_string(&path) else { continue };
+        let generated = content.contains("export PATH=\"/usr/local/bin:/opt/homebrew/bin:/usr/bin:/home/linuxbrew/.linuxbrew/bin:$PATH\"")
+            || content.contains("set \"PATH=C:\\Program Files\\nodejs;C:\\Program Files (x86)\\nodejs;%APPDATA%\\npm;%PATH%\"");
+        if generated {
+            let _ = std::fs::remove_file(&path);
         }
-        
-        Ok(script_path)
     }
 }
 
 // Data persistence functions
-pub fn get_data_dir() -> std::path::PathBuf {
+
+pub const DATA_DIR_ENV: &str = "LIBRARY_BUILD_MANAGEMENT_DIR";
+pub const PROFILE_ENV: &str = "LIBRARY_BUILD_MANAGEMENT_PROFILE";
+pub const DEFAULT_PROFILE: &str = "default";
+
+// Set once from --config-dir and --profile
+static DATA_DIR_OVERRIDE: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+static PROFILE_OVERRIDE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+pub fn set_data_dir_override(path: std::path::PathBuf) {
+    let _ = DATA_DIR_OVERRIDE.set(path);
+}
+
+pub fn set_profile_override(profile: String) {
+    let _ = PROFILE_OVERRIDE.set(profile);
+}
+
+fn default_data_dir() -> std::path::PathBuf {
     let mut path = dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
     path.push(".library-build-management");
+    path
+}
+
+// Remembers the data folder chosen in Settings; always kept in the default folder
+fn data_dir_pointer_file() -> std::path::PathBuf {
+    default_data_dir().join("data-dir")
+}
+
+// Where the data folder comes from: --config-dir, the environment, Settings, or the default
+pub fn data_dir_source() -> DataDirSource {
+    if DATA_DIR_OVERRIDE.get().is_some() {
+        DataDirSource::Flag
+    } else if std::env::var_os(DATA_DIR_ENV).is_some_and(|dir| !dir.is_empty()) {
+        DataDirSource::Environment
+    } else if chosen_data_dir().is_some() {
+        DataDirSource::Settings
+    } else {
+        DataDirSource::Default
+    }
+}
+
+fn chosen_data_dir() -> Option<std::path::PathBuf> {
+    let content = std::fs::read_to_string(data_dir_pointer_file()).ok()?;
+    let dir = content.trim();
+    (!dir.is_empty()).then(|| std::path::PathBuf::from(dir))
+}
+
+pub fn get_data_dir() -> std::path::PathBuf {
+    let path = match data_dir_source() {
+        DataDirSource::Flag => DATA_DIR_OVERRIDE.get().cloned().unwrap_or_else(default_data_dir),
+        DataDirSource::Environment => std::env::var_os(DATA_DIR_ENV).map(std::path::PathBuf::from).unwrap_or_else(default_data_dir),
+        DataDirSource::Settings => chosen_data_dir().unwrap_or_else(default_data_dir),
+        DataDirSource::Default => default_data_dir(),
+    };
     if !path.exists() {
         std::fs::create_dir_all(&path).unwrap_or_else(|e| {
             eprintln!("Failed to create data directory: {}", e);
@@ -301,67 +483,410 @@ pub fn get_data_dir() -> std::path::PathBuf {
     path
 }
 
+// Choose the data folder from Settings; None goes back to the default
+// Data in the previous folder stays where it is
+pub fn set_data_dir(dir: Option<&str>) -> Result<(), String> {
+    let pointer = data_dir_pointer_file();
+    match dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir, e))?;
+            std::fs::create_dir_all(default_data_dir())
+                .map_err(|e| format!("Failed to create data directory: {}", e))?;
+            write_file_atomically(&pointer, dir.as_bytes())
+        }
+        None if pointer.exists() => std::fs::remove_file(&pointer)
+            .map_err(|e| format!("Failed to reset data directory: {}", e)),
+        None => Ok(()),
+    }
+}
+
+// Profiles
+
+// The profile in use: --profile, the environment, or the one last chosen in the GUI
+// A name that is misspelled or not a plain folder name is never used: it could point
+// outside the profiles folder or quietly start an empty store
+pub fn active_profile() -> String {
+    if let Some(profile) = PROFILE_OVERRIDE.get() {
+        return profile.clone();
+    }
+    if let Some(profile) = env_profile().filter(|profile| ensure_profile_exists(profile).is_ok()) {
+        return profile;
+    }
+    let profile = load_settings().active_profile;
+    if ensure_profile_exists(&profile).is_ok() {
+        profile
+    } else {
+        DEFAULT_PROFILE.to_string()
+    }
+}
+
+fn env_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV).ok()
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| !profile.is_empty())
+}
+
+// The profile named in the environment is one that can be used, checked the same
+// way as --profile; active_profile ignores it otherwise
+pub fn check_env_profile() -> Result<(), String> {
+    match env_profile() {
+        Some(profile) => ensure_profile_exists(&profile).map_err(|e| format!("{} (from {})", e, PROFILE_ENV)),
+        None => Ok(()),
+    }
+}
+
+// The default profile lives directly in the data folder, so stores from before
+// profiles existed keep working; the others get a folder of their own
+fn get_profile_dir(profile: &str) -> std::path::PathBuf {
+    let data_dir = get_data_dir();
+    if profile == DEFAULT_PROFILE {
+        data_dir
+    } else {
+        data_dir.join("profiles").join(profile)
+    }
+}
+
+pub fn list_profiles() -> Vec<String> {
+    let mut profiles = vec![DEFAULT_PROFILE.to_string()];
+    if let Ok(entries) = std::fs::read_dir(get_data_dir().join("profiles")) {
+        let mut named: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| validate_profile_name(name).is_ok())
+            .collect();
+        named.sort();
+        profiles.extend(named);
+    }
+    profiles
+}
+
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("Invalid profile name '{}': use letters, digits, '-' and '_'", name));
+    }
+    Ok(())
+}
+
+pub fn create_profile(name: &str) -> Result<(), String> {
+    validate_profile_name(name)?;
+    if list_profiles().iter().any(|p| p == name) {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+    std::fs::create_dir_all(get_profile_dir(name))
+        .map_err(|e| format!("Failed to create profile '{}': {}", name, e))
+}
+
+pub fn ensure_profile_exists(name: &str) -> Result<(), String> {
+    validate_profile_name(name)?;
+    if list_profiles().iter().any(|p| p == name) {
+        Ok(())
+    } else {
+        Err(format!("Profile '{}' does not exist (available: {})", name, list_profiles().join(", ")))
+    }
+}
+
+// Make a profile the one the GUI, and CLI runs without --profile, use
+pub fn switch_profile(name: &str) -> Result<(), String> {
+    ensure_profile_exists(name)?;
+    let mut settings = load_settings();
+    settings.active_profile = name.to_string();
+    save_settings(&settings)
+}
+
 pub fn get_projects_file() -> std::path::PathBuf {
-    let mut path = get_data_dir();
+    let mut path = get_profile_dir(&active_profile());
+    if !path.exists() {
+        std::fs::create_dir_all(&path).unwrap_or_else(|e| {
+            eprintln!("Failed to create profile directory: {}", e);
+        });
+    }
     path.push("projects.json");
     path
 }
 
+// Projects from the store, or none when it cannot be read
+// A store that cannot be read is never written to, see update_projects
 pub fn load_projects() -> Vec<Project> {
+    load_project_store().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        vec![]
+    })
+}
+
+// Read the store, migrating an older layout in place after backing the file up
+pub fn load_project_store() -> Result<Vec<Project>, String> {
+    let _lock = lock_project_store()?;
+    read_project_store()
+}
+
+// Held while the store is read, changed and written back, so that the GUI and
+// the CLI never interleave their read-modify-write cycles; released on drop
+fn lock_project_store() -> Result<std::fs::File, String> {
+    let lock_path = get_projects_file().with_file_name("projects.json.lock");
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("Failed to open {}: {}", lock_path.display(), e))?;
+    file.lock().map_err(|e| format!("Failed to lock the projects file: {}", e))?;
+    Ok(file)
+}
+
+// Callers must hold the store lock, a migration writes the file
+fn read_project_store() -> Result<Vec<Project>, String> {
     let file_path = get_projects_file();
     if !file_path.exists() {
-        return vec![];
+        return Ok(vec![]);
     }
     
-    match std::fs::read_to_string(&file_path) {
-        Ok(content) => {
-            match serde_json::from_str::<Vec<Project>>(&content) {
-                Ok(projects) => projects,
-                Err(e) => {
-                    eprintln!("Failed to parse projects file: {}", e);
-                    vec![]
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read projects file: {}", e))?;
+    let (projects, found_version) = parse_project_store(&content)?;
+    
+    if found_version < PROJECT_STORE_VERSION {
+        let backup = backup_project_store(found_version)?;
+        write_project_store(&projects)?;
+        eprintln!("Migrated projects file from schema v{} to v{} (backup: {:?})", found_version, PROJECT_STORE_VERSION, backup);
+    }
+    Ok(projects)
+}
+
+// Parse any known layout of the store
+// Returns the projects and the schema version the file was written with
+pub fn parse_project_store(content: &str) -> Result<(Vec<Project>, u32), String> {
+    let value = serde_json::from_str::<serde_json::Value>(content)
+        .map_err(|e| format!("Projects file is not valid JSON: {}", e))?;
+    parse_project_store_value(value)
+}
+
+// Same as parse_project_store, for a store already parsed from JSON or TOML
+fn parse_project_store_value(mut value: serde_json::Value) -> Result<(Vec<Project>, u32), String> {
+    let found_version = match &value {
+        serde_json::Value::Array(_) => 1,
+        serde_json::Value::Object(store) => store.get("version")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| "Projects file has no schema version".to_string())?,
+        _ => return Err("Projects file is neither a project list nor a versioned store".to_string()),
+    };
+    if found_version == 0 || found_version > PROJECT_STORE_VERSION {
+        return Err(format!(
+            "Projects file uses schema v{}, this version of the app understands up to v{}",
+            found_version, PROJECT_STORE_VERSION
+        ));
+    }
+    
+    for version in found_version..PROJECT_STORE_VERSION {
+        value = migrate_project_store(version, value);
+    }
+    
+    let store = serde_json::from_value::<ProjectStore>(value)
+        .map_err(|e| format!("Projects file does not match schema v{}: {}", found_version, e))?;
+    Ok((store.projects, found_version))
+}
+
+// One step of the migration chain: a store in layout `from` to layout `from + 1`
+fn migrate_project_store(from: u32, value: serde_json::Value) -> serde_json::Value {
+    match from {
+        // Bare array -> versioned envelope
+        1 => serde_json::json!({ "version": 2, "projects": value }),
+        // Selected script names (and their timeouts) -> typed build steps
+        2 => {
+            let mut value = value;
+            if let Some(projects) = value.get_mut("projects").and_then(|p| p.as_array_mut()) {
+                for project in projects.iter_mut().filter_map(|p| p.as_object_mut()) {
+                    let timeouts = project.remove("command_timeouts").unwrap_or_default();
+                    let steps: Vec<serde_json::Value> = project.remove("selected_build_commands")
+                        .and_then(|names| names.as_array().cloned())
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|name| name.as_str().map(str::to_string))
+                        .map(|name| serde_json::json!({
+                            "kind": { "type": "script", "name": name, "args": [] },
+                            "timeout_secs": timeouts.get(&name),
+                        }))
+                        .collect();
+                    project.insert("build_steps".to_string(), serde_json::Value::Array(steps));
                 }
             }
+            value["version"] = serde_json::json!(3);
+            value
         }
-        Err(e) => {
-            eprintln!("Failed to read projects file: {}", e);
-            vec![]
-        }
+        _ => value,
     }
 }
 
-pub fn save_projects(projects: &[Project]) {
-    let file_path = get_projects_file();
-    match serde_json::to_string_pretty(projects) {
-        Ok(content) => {
-            if let Err(e) = std::fs::write(&file_path, content) {
-                eprintln!("Failed to save projects: {}", e);
-            } else {
-                println!("Saved {} projects to {:?}", projects.len(), file_path);
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to serialize projects: {}", e);
-        }
+// Copy the store aside before it is rewritten in a newer layout
+fn backup_project_store(version: u32) -> Result<std::path::PathBuf, String> {
+    let backup = get_projects_file().with_file_name(format!(
+        "projects.v{}.{}.json.bak",
+        version,
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+    std::fs::copy(get_projects_file(), &backup)
+        .map_err(|e| format!("Failed to back up projects file before migrating it: {}", e))?;
+    Ok(backup)
+}
+
+fn write_project_store(projects: &[Project]) -> Result<(), String> {
+    let store = ProjectStore { version: PROJECT_STORE_VERSION, projects: projects.to_vec() };
+    let content = serde_json::to_string_pretty(&store)
+        .map_err(|e| format!("Failed to serialize projects: {}", e))?;
+    write_file_atomically(&get_projects_file(), content.as_bytes())
+        .map_err(|e| format!("Failed to save projects: {}", e))
+}
+
+// Write through a fully written sibling file that is then renamed over the
+// target, so readers see either the old or the new content, never a mix
+pub fn write_file_atomically(path: &std::path::Path, content: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+    
+    let name = path.file_name()
+        .ok_or_else(|| format!("{:?} has no file name", path))?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.tmp-{}", name, std::process::id()));
+    
+    let written = std::fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content)?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp_path, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    written.map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// Read the store, apply a change to the whole project list and write it back,
+// all under the store lock; nothing is written when the change fails
+// The file on disk may be the user's only copy of a configuration we failed to
+// read, so an unreadable store is never replaced
+pub fn update_projects<T>(change: impl FnOnce(&mut Vec<Project>) -> Result<T, String>) -> Result<T, String> {
+    let _lock = lock_project_store()?;
+    let mut projects = read_project_store()
+        .map_err(|e| format!("Refusing to save projects over a file that cannot be read: {}", e))?;
+    let result = change(&mut projects)?;
+    write_project_store(&projects)?;
+    Ok(result)
+}
+
+// Apply a change to one stored project; other projects, and fields the change
+// does not touch, keep whatever is on disk
+pub fn update_project(project_id: &str, change: impl FnOnce(&mut Project)) {
+    let result = update_projects(|projects| {
+        let project = projects.iter_mut()
+            .find(|p| p.id == project_id)
+            .ok_or_else(|| format!("Project '{}' no longer exists", project_id))?;
+        change(project);
+        Ok(())
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+    }
+}
+
+
+// Backups of the store left by migrations and recoveries, newest first
+pub fn list_project_store_backups() -> Vec<std::path::PathBuf> {
+    let Some(store_dir) = get_projects_file().parent().map(|dir| dir.to_path_buf()) else { return vec![] };
+    let Ok(entries) = std::fs::read_dir(store_dir) else { return vec![] };
+    let mut backups: Vec<(std::time::SystemTime, std::path::PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.starts_with("projects.") && (name.ends_with(".bak") || name.contains(".broken-"))
+        })
+        .map(|e| (e.metadata().and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH), e.path()))
+        .collect();
+    backups.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+// Move the unreadable store out of the way so the app can start over
+// Returns where the old file was kept
+pub fn set_aside_project_store() -> Result<std::path::PathBuf, String> {
+    let _lock = lock_project_store()?;
+    set_aside_project_store_locked()
+}
+
+// Callers must hold the store lock
+fn set_aside_project_store_locked() -> Result<std::path::PathBuf, String> {
+    let aside = get_projects_file().with_file_name(format!(
+        "projects.broken-{}.json",
+        chrono::Local::now().format("%Y%m%d%H%M%S")
+    ));
+    std::fs::rename(get_projects_file(), &aside)
+        .map_err(|e| format!("Failed to move projects file aside: {}", e))?;
+    Ok(aside)
+}
+
+// Replace the unreadable store with a backup, keeping the current file aside
+pub fn restore_project_store_backup(backup: &std::path::Path) -> Result<usize, String> {
+    let content = std::fs::read_to_string(backup)
+        .map_err(|e| format!("Failed to read {}: {}", backup.display(), e))?;
+    let (projects, _) = parse_project_store(&content)
+        .map_err(|e| format!("{} cannot be restored: {}", backup.display(), e))?;
+    
+    // One lock for both steps, so no other writer sees the store missing in between
+    let _lock = lock_project_store()?;
+    if get_projects_file().exists() {
+        set_aside_project_store_locked()?;
+    }
+    write_project_store(&projects)?;
+    Ok(projects.len())
+}
+
+pub fn get_settings_file() -> std::path::PathBuf {
+    let mut path = get_data_dir();
+    path.push("settings.json");
+    path
+}
+
+pub fn load_settings() -> AppSettings {
+    match std::fs::read_to_string(get_settings_file()) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            eprintln!("Failed to parse settings file: {}", e);
+            AppSettings::default()
+        }),
+        Err(_) => AppSettings::default(),
     }
 }
 
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    write_file_atomically(&get_settings_file(), content.as_bytes())
+        .map_err(|e| format!("Failed to save settings: {}", e))
+}
+
 // Delete a project by name
 pub fn delete_project(project_name: &str) -> Result<(), String> {
-    let mut projects = load_projects();
-    let initial_count = projects.len();
-    
-    // Remove the project with the matching name
-    projects.retain(|p| p.name != project_name);
+    let removed_ids = update_projects(|projects| {
+        let removed_ids: Vec<String> = projects.iter()
+            .filter(|p| p.name == project_name)
+            .map(|p| p.id.clone())
+            .collect();
+        
+        if removed_ids.is_empty() {
+            return Err(format!("Project '{}' not found", project_name));
+        }
+        
+        // Remove the project with the matching name
+        projects.retain(|p| p.name != project_name);
+        Ok(removed_ids)
+    })?;
     
-    if projects.len() == initial_count {
-        return Err(format!("Project '{}' not found", project_name));
+    // Build history and backups belong to the project, drop them too
+    for id in removed_ids {
+        let _ = std::fs::remove_dir_all(get_history_dir(&id));
+        let _ = std::fs::remove_dir_all(get_backups_dir(&id));
     }
     
-    // Save the updated projects list
-    save_projects(&projects);
-    
-    println!("✅ Deleted project '{}'", project_name);
+    eprintln!("✅ Deleted project '{}'", project_name);
     Ok(())
 }
 
@@ -370,34 +895,47 @@ pub fn delete_project(project_name: &str) -> Result<(), String> {
 
 // Package.json parsing functions
 pub fn parse_package_json(project_path: &str) -> Vec<String> {
-    let mut package_path = std::path::PathBuf::from(project_path);
-    package_path.push("package.json");
-    
+    read_package_scripts(project_path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        vec![]
+    })
+}
+
+// Script names of a folder's package.json; none when there is no package.json,
+// an error when it cannot be read (e.g. half-saved in an editor)
+pub fn read_package_scripts(project_path: &str) -> Result<Vec<String>, String> {
+    let package_path = std::path::Path::new(project_path).join("package.json");
     if !package_path.exists() {
-        return vec![];
+        return Ok(vec![]);
     }
     
-    match std::fs::read_to_string(&package_path) {
-        Ok(content) => {
-            match serde_json::from_str::<serde_json::Value>(&content) {
-                Ok(json) => {
-                    if let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) {
-                        scripts.keys().cloned().collect()
-                    } else {
-                        vec![]
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Failed to parse package.json: {}", e);
-                    vec![]
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to read package.json: {}", e);
-            vec![]
-        }
+    let content = std::fs::read_to_string(&package_path)
+        .map_err(|e| format!("Failed to read package.json: {}", e))?;
+    let json = serde_json::from_str::<serde_json::Value>(&content)
+        .map_err(|e| format!("Failed to parse package.json: {}", e))?;
+    Ok(json.get("scripts")
+        .and_then(|s| s.as_object())
+        .map(|scripts| scripts.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+// Store the scripts currently in the project's package.json; returns the
+// updated project when they differ from the ones last seen
+pub fn sync_build_commands(project: &Project) -> Option<Project> {
+    let scripts = read_package_scripts(&project.path).ok()?;
+    if scripts == project.build_commands {
+        return None;
     }
+    update_project(&project.id, |p| p.build_commands = scripts.clone());
+    Some(Project { build_commands: scripts, ..project.clone() })
+}
+
+// Selected scripts that are not among `scripts` any more, e.g. renamed or deleted
+pub fn stale_build_steps<'a>(project: &'a Project, scripts: &[String]) -> Vec<&'a str> {
+    project.build_steps.iter()
+        .filter_map(|step| step.script_name())
+        .filter(|name| !scripts.iter().any(|script| script == name))
+        .collect()
 }
 
 pub fn get_package_version(package_path: &str) -> Option<String> {
@@ -429,39 +967,379 @@ pub fn get_package_version(package_path: &str) -> Option<String> {
     }
 }
 
+// The `name` field of a folder's package.json
+pub fn get_package_name(package_path: &str) -> Option<String> {
+    let content = std::fs::read_to_string(std::path::Path::new(package_path).join("package.json")).ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    json.get("name").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
 // Project management functions
 pub fn create_project(name: String, path: String) -> Project {
     let build_commands = parse_package_json(&path);
+    let output_dirs = infer_output_dirs(&path);
     Project {
         id: uuid::Uuid::new_v4().to_string(),
         name,
         path,
         build_commands,
-        selected_build_commands: vec![], // Start with empty ordered list
+        build_steps: vec![], // Start with empty ordered list
         target_paths: vec![],
+        package_manager: None,
+        output_dirs,
+        version_bump: VersionBump::default(),
+        watch: WatchSettings::default(),
+        workspace: None,
+        env: vec![],
+        env_files: vec![],
+        build_timeout_secs: None,
     }
 }
 
+// Workspaces
 
-
-// Version management functions
-pub fn increment_patch_version(version: &str) -> String {
-    let parts: Vec<&str> = version.split('.').collect();
-    if parts.len() >= 3 {
-        let major = parts[0];
-        let minor = parts[1];
-        let patch: u32 = parts[2].parse().unwrap_or(0);
-        format!("{}.{}.{}", major, minor, patch + 1)
-    } else {
-        format!("{}.0.1", version)
+// Package folder globs of a workspace root, from package.json `workspaces`
+// (array or `{ packages: [...] }`) or pnpm-workspace.yaml; None if it is not a workspace root
+pub fn workspace_patterns(root: &str) -> Option<Vec<String>> {
+    let root_dir = std::path::Path::new(root);
+    
+    if let Ok(content) = std::fs::read_to_string(root_dir.join("pnpm-workspace.yaml")) {
+        return Some(parse_pnpm_workspace_packages(&content));
     }
+    
+    let content = std::fs::read_to_string(root_dir.join("package.json")).ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    let workspaces = json.get("workspaces")?;
+    let list = workspaces.as_array()
+        .or_else(|| workspaces.get("packages").and_then(|p| p.as_array()))?;
+    Some(list.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
 }
 
-pub fn update_package_version(package_path: &str, new_version: &str) -> Result<(), String> {
-    let mut path = std::path::PathBuf::from(package_path);
-    path.push("package.json");
+// Read the `packages:` list of pnpm-workspace.yaml; only that key is needed, so
+// this handles its block and flow list forms instead of pulling in a YAML parser
+fn parse_pnpm_workspace_packages(content: &str) -> Vec<String> {
+    let unquote = |value: &str| value.trim().trim_matches(|c| c == '\'' || c == '"').to_string();
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
     
-    if !path.exists() {
+    for line in content.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        
+        if !line.starts_with(' ') && !line.starts_with('-') {
+            in_packages = false;
+            if let Some(rest) = line.strip_prefix("packages:") {
+                let rest = rest.trim();
+                if let Some(flow) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+                    patterns.extend(flow.split(',').map(unquote).filter(|p| !p.is_empty()));
+                } else {
+                    in_packages = true;
+                }
+            }
+            continue;
+        }
+        
+        if in_packages {
+            if let Some(item) = line.trim_start().strip_prefix('-') {
+                patterns.push(unquote(item));
+            }
+        }
+    }
+    patterns
+}
+
+// Every package below a workspace root that matches the workspace globs
+// Negated globs (`!packages/internal-*`) exclude packages; node_modules is never searched
+pub fn find_workspace_packages(root: &str) -> Result<Vec<WorkspacePackage>, String> {
+    let patterns = workspace_patterns(root)
+        .ok_or_else(|| format!("{} is not a workspace root (no `workspaces` in package.json and no pnpm-workspace.yaml)", root))?;
+    
+    let mut include = globset::GlobSetBuilder::new();
+    let mut exclude = globset::GlobSetBuilder::new();
+    for pattern in &patterns {
+        let (set, pattern) = match pattern.strip_prefix('!') {
+            Some(negated) => (&mut exclude, negated),
+            None => (&mut include, pattern.as_str()),
+        };
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let glob = globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| format!("Invalid workspace glob {:?}: {}", pattern, e))?;
+        set.add(glob);
+    }
+    let include = include.build().map_err(|e| format!("Invalid workspace globs: {}", e))?;
+    let exclude = exclude.build().map_err(|e| format!("Invalid workspace globs: {}", e))?;
+    
+    fn walk(dir: &std::path::Path, root: &std::path::Path, folders: &mut Vec<(String, std::path::PathBuf)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else { return };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !path.is_dir() || name == "node_modules" || name.starts_with('.') {
+                continue;
+            }
+            if let Ok(relative) = path.strip_prefix(root) {
+                let relative = relative.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                folders.push((relative, path.clone()));
+            }
+            walk(&path, root, folders);
+        }
+    }
+    
+    let root_dir = std::path::Path::new(root);
+    let mut folders = Vec::new();
+    walk(root_dir, root_dir, &mut folders);
+    
+    let mut packages: Vec<WorkspacePackage> = folders.into_iter()
+        .filter(|(relative, path)| {
+            include.is_match(relative) && !exclude.is_match(relative) && path.join("package.json").exists()
+        })
+        .filter_map(|(_, path)| {
+            let path = path.to_string_lossy().to_string();
+            let content = std::fs::read_to_string(std::path::Path::new(&path).join("package.json")).ok()?;
+            let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+            let name = json.get("name").and_then(|v| v.as_str())?.to_string();
+            Some(WorkspacePackage { name, version: get_package_version(&path), path })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
+
+// A project for a workspace package, linked to its workspace root
+pub fn create_workspace_project(root: &str, package: &WorkspacePackage, scope: BuildScope) -> Project {
+    let mut project = create_project(package.name.clone(), package.path.clone());
+    project.workspace = Some(WorkspaceLink {
+        root: root.to_string(),
+        package_name: package.name.clone(),
+        scope,
+    });
+    project
+}
+
+// Output directory functions
+
+// Infer the build output folders from package.json `main`/`module`/`exports`/`files`
+// Only the top-level folder of each entry is kept, e.g. "./lib/esm/index.js" -> "lib"
+// Falls back to "dist" when nothing points into a folder
+pub fn infer_output_dirs(project_path: &str) -> Vec<OutputDir> {
+    let mut package_path = std::path::PathBuf::from(project_path);
+    package_path.push("package.json");
+    
+    let json = std::fs::read_to_string(&package_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+    
+    let mut entries: Vec<String> = Vec::new();
+    let mut files: Vec<String> = Vec::new();
+    if let Some(json) = json {
+        for field in ["main", "module", "types", "typings", "browser"] {
+            if let Some(value) = json.get(field).and_then(|v| v.as_str()) {
+                entries.push(value.to_string());
+            }
+        }
+        if let Some(exports) = json.get("exports") {
+            collect_export_paths(exports, &mut entries);
+        }
+        if let Some(list) = json.get("files").and_then(|f| f.as_array()) {
+            files.extend(list.iter().filter_map(|f| f.as_str()).map(|f| f.to_string()));
+        }
+    }
+    
+    // Entry points name files, so only entries inside a folder count
+    let mut dirs: Vec<String> = Vec::new();
+    for entry in &entries {
+        let trimmed = entry.trim_start_matches("./");
+        if trimmed.contains('/') {
+            push_top_level_dir(trimmed, &mut dirs);
+        }
+    }
+    
+    // `files` lists folders directly; it often includes sources too, so it
+    // is only used when the entry points did not tell us anything
+    if dirs.is_empty() {
+        for entry in &files {
+            let trimmed = entry.trim_start_matches("./");
+            // Skip negated globs and plain root files like README.md
+            if trimmed.starts_with('!') || (!trimmed.contains('/') && trimmed.contains('.')) {
+                continue;
+            }
+            push_top_level_dir(trimmed, &mut dirs);
+        }
+    }
+    
+    if dirs.is_empty() {
+        dirs.push("dist".to_string());
+    }
+    
+    dirs.into_iter()
+        .map(|dir| OutputDir { source: dir.clone(), destination: dir })
+        .collect()
+}
+
+fn push_top_level_dir(path: &str, dirs: &mut Vec<String>) {
+    let first = path.split('/').next().unwrap_or("");
+    if first.is_empty() || first == "." || first == ".." || first.contains('*') {
+        return;
+    }
+    if !dirs.iter().any(|d| d == first) {
+        dirs.push(first.to_string());
+    }
+}
+
+// Recursively collect the string values of a package.json `exports` map
+fn collect_export_paths(value: &serde_json::Value, entries: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(path) => entries.push(path.clone()),
+        serde_json::Value::Object(map) => {
+            for nested in map.values() {
+                collect_export_paths(nested, entries);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for nested in items {
+                collect_export_paths(nested, entries);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Output directories to use for a project: the configured list or the inferred default
+pub fn resolve_output_dirs(project: &Project) -> Vec<OutputDir> {
+    if project.output_dirs.is_empty() {
+        infer_output_dirs(&project.path)
+    } else {
+        project.output_dirs.clone()
+    }
+}
+
+// Make sure every output directory exists after the build
+fn verify_output_dirs(project_path: &std::path::Path, output_dirs: &[OutputDir]) -> Result<(), String> {
+    // An empty entry would copy the whole project (or wipe the whole target)
+    if output_dirs.iter().any(|dir| dir.source.trim().is_empty() || dir.destination.trim().is_empty()) {
+        return Err("Build output settings contain an empty folder. Fill it in or remove it.".to_string());
+    }
+    
+    let missing: Vec<&str> = output_dirs.iter()
+        .filter(|dir| !project_path.join(&dir.source).exists())
+        .map(|dir| dir.source.as_str())
+        .collect();
+    
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Output directories not found after build: {}. Build commands may have failed or the build output settings are wrong.", missing.join(", ")))
+    }
+}
+
+// Version management functions
+
+// Parse a version leniently: npm tolerates a leading "v" or "=" and surrounding spaces
+pub fn parse_version(version: &str) -> Result<semver::Version, String> {
+    let cleaned = version.trim().trim_start_matches(['v', '=']);
+    semver::Version::parse(cleaned)
+        .map_err(|e| format!("Invalid version '{}': {}", version, e))
+}
+
+// Compute the next version of a target from its current version and the source version
+// Bumps follow npm semantics: bumping a prerelease releases it (1.4.2-beta.3 → 1.4.2 for patch)
+// Build metadata of the current version is kept
+pub fn bump_version(current: &str, source: &str, strategy: &VersionBump) -> Result<String, String> {
+    match strategy {
+        VersionBump::KeepSource => return parse_version(source).map(|v| v.to_string()),
+        // Written back exactly as it was, whether or not it is valid semver
+        VersionBump::Unchanged => return Ok(current.to_string()),
+        _ => {}
+    }
+    
+    let mut version = parse_version(current)?;
+    let is_prerelease = !version.pre.is_empty();
+    
+    match strategy {
+        VersionBump::Patch => {
+            if !is_prerelease {
+                version.patch += 1;
+            }
+            version.pre = semver::Prerelease::EMPTY;
+        }
+        VersionBump::Minor => {
+            if !is_prerelease || version.patch != 0 {
+                version.minor += 1;
+            }
+            version.patch = 0;
+            version.pre = semver::Prerelease::EMPTY;
+        }
+        VersionBump::Major => {
+            if !is_prerelease || version.minor != 0 || version.patch != 0 {
+                version.major += 1;
+            }
+            version.minor = 0;
+            version.patch = 0;
+            version.pre = semver::Prerelease::EMPTY;
+        }
+        VersionBump::Prerelease { identifier } => {
+            let prefix = format!("{}.", identifier);
+            let next_number = version.pre.as_str()
+                .strip_prefix(&prefix)
+                .and_then(|n| n.parse::<u64>().ok())
+                .map(|n| n + 1);
+            
+            let number = match next_number {
+                Some(n) => n,
+                None => {
+                    // A release becomes a prerelease of the next patch
+                    if !is_prerelease {
+                        version.patch += 1;
+                    }
+                    0
+                }
+            };
+            version.pre = semver::Prerelease::new(&format!("{}{}", prefix, number))
+                .map_err(|e| format!("Invalid prerelease identifier '{}': {}", identifier, e))?;
+        }
+        VersionBump::TimestampPrerelease { identifier } => {
+            if !is_prerelease {
+                version.patch += 1;
+            }
+            let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S");
+            version.pre = semver::Prerelease::new(&format!("{}.{}", identifier, timestamp))
+                .map_err(|e| format!("Invalid prerelease identifier '{}': {}", identifier, e))?;
+        }
+        VersionBump::KeepSource | VersionBump::Unchanged => unreachable!(),
+    }
+    
+    Ok(version.to_string())
+}
+
+// Strategy that applies to a target: its own override or the project's default
+pub fn resolve_version_bump(project: &Project, target: &TargetPath) -> VersionBump {
+    target.version_bump.clone().unwrap_or_else(|| project.version_bump.clone())
+}
+
+// Current and next version of a target, used both for the build and to preview it
+pub fn plan_target_version(project: &Project, target: &TargetPath) -> Result<(String, String), String> {
+    let source_version = get_package_version(&project.path)
+        .unwrap_or_else(|| "0.0.0".to_string());
+    // A target without package.json starts from the source version
+    let current_version = get_package_version(&target.path)
+        .unwrap_or_else(|| source_version.clone());
+    let strategy = resolve_version_bump(project, target);
+    let new_version = bump_version(&current_version, &source_version, &strategy)?;
+    Ok((current_version, new_version))
+}
+
+pub fn update_package_version(package_path: &str, new_version: &str) -> Result<(), String> {
+    let mut path = std::path::PathBuf::from(package_path);
+    path.push("package.json");
+    
+    if !path.exists() {
         return Err("package.json not found".to_string());
     }
     
@@ -527,133 +1405,210 @@ fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<()
         if src_path.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else {
-            std::fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("Failed to copy file {:?} to {:?}: {}", src_path, dst_path, e))?;
+            copy_file(&src_path, &dst_path)?;
         }
     }
     
     Ok(())
 }
 
-// Native dialogs
-pub async fn open_folder_dialog() -> Option<String> {
-    let folder = rfd::AsyncFileDialog::new()
-        .set_title("Select Project Folder")
-        .pick_folder()
-        .await;
+// Copy one file and give the copy the source's modification time, so later syncs
+// can tell unchanged files apart without reading them
+fn copy_file(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {:?}: {}", parent, e))?;
+    }
+    std::fs::copy(src, dst)
+        .map_err(|e| format!("Failed to copy file {:?} to {:?}: {}", src, dst, e))?;
     
-    folder.map(|f| f.path().to_string_lossy().to_string())
+    let modified = std::fs::metadata(src).and_then(|meta| meta.modified());
+    if let Ok(modified) = modified {
+        let _ = std::fs::File::options().write(true).open(dst)
+            .and_then(|file| file.set_modified(modified));
+    }
+    Ok(())
 }
 
+// Import and export
 
+// Bundles ending in .toml are TOML, anything else JSON
+pub fn bundle_format_for(path: &std::path::Path) -> BundleFormat {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("toml") => BundleFormat::Toml,
+        _ => BundleFormat::Json,
+    }
+}
 
-// Main build and update logic
-pub async fn build_and_update_project(project: &Project) -> Result<String, String> {
-    if project.selected_build_commands.is_empty() {
-        return Err("No build commands selected".to_string());
+// Secret variable values are masked unless `include_secrets` is set, since
+// bundles are meant to be shared
+pub fn export_projects(projects: &[Project], format: BundleFormat, include_secrets: bool) -> Result<String, String> {
+    let bundle = ProjectBundle {
+        version: PROJECT_STORE_VERSION,
+        exported_at: chrono::Local::now(),
+        projects: if include_secrets { projects.to_vec() } else { projects.iter().map(mask_secret_env).collect() },
+    };
+    match format {
+        BundleFormat::Json => serde_json::to_string_pretty(&bundle)
+            .map_err(|e| format!("Failed to serialize projects: {}", e)),
+        BundleFormat::Toml => toml::to_string_pretty(&bundle)
+            .map_err(|e| format!("Failed to serialize projects: {}", e)),
     }
-    
-    let active_targets: Vec<_> = project.target_paths.iter()
-        .filter(|p| p.is_active)
-        .collect();
-    
-    if active_targets.is_empty() {
-        return Err("No active target paths".to_string());
+}
+
+pub fn write_project_bundle(path: &std::path::Path, projects: &[Project], format: BundleFormat, include_secrets: bool) -> Result<(), String> {
+    let content = export_projects(projects, format, include_secrets)?;
+    write_file_atomically(path, content.as_bytes())
+}
+
+// Projects from a bundle; a plain projects.json, in any of its layouts, reads as a bundle too
+pub fn read_project_bundle(path: &std::path::Path, format: BundleFormat) -> Result<Vec<Project>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let value = match format {
+        BundleFormat::Json => serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|e| format!("{} is not valid JSON: {}", path.display(), e))?,
+        BundleFormat::Toml => toml::from_str::<serde_json::Value>(&content)
+            .map_err(|e| format!("{} is not valid TOML: {}", path.display(), e))?,
+    };
+    parse_project_store_value(value)
+        .map(|(projects, _)| projects)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// Parse "FROM=TO" into a path remapping
+pub fn parse_path_remap(remap: &str) -> Result<PathRemap, String> {
+    let (from, to) = remap.split_once('=')
+        .ok_or_else(|| format!("Invalid path remapping '{}', expected FROM=TO", remap))?;
+    // Trailing separators would stop "/Users/alice/" from matching "/Users/alice"
+    let from = from.trim().trim_end_matches(['/', '\\']);
+    if from.is_empty() {
+        return Err(format!("Invalid path remapping '{}', FROM cannot be empty or a root", remap));
     }
-    
-    let project_path = std::path::Path::new(&project.path);
-    let package_json_path = project_path.join("package.json");
-    
-    // Check if package.json exists
-    if !package_json_path.exists() {
-        return Err("package.json not found in project directory".to_string());
+    Ok(PathRemap {
+        from: from.to_string(),
+        to: to.trim().trim_end_matches(['/', '\\']).to_string(),
+    })
+}
+
+// Apply the first remapping whose prefix matches, on whole path components only
+fn remap_path(path: &str, remaps: &[PathRemap]) -> String {
+    for remap in remaps {
+        if let Some(rest) = path.strip_prefix(&remap.from) {
+            if rest.is_empty() || rest.starts_with(['/', '\\']) {
+                return format!("{}{}", remap.to, rest);
+            }
+        }
     }
-    
-    let mut results = Vec::new();
-    
-    // Step 1: Execute build commands using bash script
-    results.push(format!("🚀 Executing {} build commands in order...", project.selected_build_commands.len()));
-    
-    // Create and execute bash script with all commands
-    match create_build_script(&project.selected_build_commands, &project.path) {
-        Ok(script_path) => {
-            // Execute the bash script
-            let output = tokio::process::Command::new("bash")
-                .arg(&script_path)
-                .current_dir(&project.path)
-                .output()
-                .await;
-            
-            // Clean up script file
-            let _ = std::fs::remove_file(&script_path);
+    path.to_string()
+}
+
+// Rewrite every absolute path stored on a project
+pub fn remap_project_paths(project: &mut Project, remaps: &[PathRemap]) {
+    project.path = remap_path(&project.path, remaps);
+    for target in project.target_paths.iter_mut() {
+        target.path = remap_path(&target.path, remaps);
+    }
+    if let Some(link) = project.workspace.as_mut() {
+        link.root = remap_path(&link.root, remaps);
+    }
+    for file in project.env_files.iter_mut() {
+        file.path = remap_path(&file.path, remaps);
+    }
+}
+
+// Add bundled projects to the store; a project conflicts with a stored one that
+// has the same ID or, ignoring case, the same name
+pub fn import_projects(
+    incoming: Vec<Project>,
+    on_conflict: ConflictPolicy,
+    remaps: &[PathRemap],
+) -> Result<Vec<ImportOutcome>, String> {
+    update_projects(|projects| {
+        let mut outcomes = Vec::new();
+        for mut project in incoming {
+            remap_project_paths(&mut project, remaps);
+            let conflict = projects.iter().position(|p| p.id == project.id)
+                .or_else(|| projects.iter().position(|p| p.name.to_lowercase() == project.name.to_lowercase()));
+            restore_masked_secrets(&mut project, conflict.map(|index| &projects[index]));
             
-            match output {
-                Ok(output) => {
-                    if output.status.success() {
-                        let stdout = String::from_utf8_lossy(&output.stdout);
-                        results.push(format!("✅ All build commands completed successfully\n{}", stdout));
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        return Err(format!("❌ Build script failed: {}", stderr));
-                    }
+            let action = match (conflict, on_conflict) {
+                (None, _) => {
+                    projects.push(project.clone());
+                    ImportAction::Added
                 }
-                Err(e) => {
-                    return Err(format!("❌ Failed to execute build script: {}", e));
+                (Some(_), ConflictPolicy::Skip) => ImportAction::Skipped,
+                (Some(index), ConflictPolicy::Overwrite) => {
+                    // Keep the stored ID so build history and backups stay attached
+                    project.id = projects[index].id.clone();
+                    projects[index] = project.clone();
+                    ImportAction::Overwritten
                 }
-            }
-        }
-        Err(e) => {
-            return Err(format!("❌ Failed to create build script: {}", e));
+                (Some(_), ConflictPolicy::Rename) => {
+                    project.id = uuid::Uuid::new_v4().to_string();
+                    project.name = unique_project_name(projects, &project.name);
+                    projects.push(project.clone());
+                    ImportAction::Renamed
+                }
+            };
+            outcomes.push(ImportOutcome { project, action });
         }
+        Ok(outcomes)
+    })
+}
+
+// Secrets left out of a bundle keep the value this machine already stores;
+// without one they come in empty rather than as the mask
+fn restore_masked_secrets(project: &mut Project, stored: Option<&Project>) {
+    for var in project.env.iter_mut().filter(|var| var.secret && var.value == SECRET_MASK) {
+        var.value = stored
+            .and_then(|stored| stored.env.iter().find(|s| s.key == var.key))
+            .map(|s| s.value.clone())
+            .unwrap_or_default();
     }
+}
+
+// "name (2)", "name (3)", ... whichever is free first
+fn unique_project_name(projects: &[Project], name: &str) -> String {
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !projects.iter().any(|p| p.name.to_lowercase() == candidate.to_lowercase()))
+        .expect("some name is free")
+}
+
+// Native dialogs
+pub async fn open_folder_dialog() -> Option<String> {
+    let folder = rfd::AsyncFileDialog::new()
+        .set_title("Select Project Folder")
+        .pick_folder()
+        .await;
     
-    results.push("\n📦 Build commands completed successfully!".to_string());
-    
-    // Step 2: Check if dist directory exists after build
-    let dist_path = project_path.join("dist");
-    if !dist_path.exists() {
-        return Err("dist directory not found after build. Build commands may have failed.".to_string());
-    }
-    
-    results.push("\n📤 Updating target paths...".to_string());
+    folder.map(|f| f.path().to_string_lossy().to_string())
+}
+
+pub async fn save_bundle_dialog(file_name: &str) -> Option<String> {
+    let file = rfd::AsyncFileDialog::new()
+        .set_title("Export Projects")
+        .set_file_name(file_name)
+        .add_filter("Project bundle", &["json", "toml"])
+        .save_file()
+        .await;
     
-    // Process each active target
-    for target in active_targets {
-        let target_path = std::path::Path::new(&target.path);
-        
-        // Get current version from target's package.json
-        let current_version = get_package_version(&target.path)
-            .unwrap_or_else(|| "0.0.0".to_string());
-        
-        // Increment patch version
-        let new_version = increment_patch_version(&current_version);
-        
-        // Copy dist directory
-        let target_dist = target_path.join("dist");
-        if let Err(e) = copy_directory(&dist_path, &target_dist) {
-            results.push(format!("❌ Failed to copy dist to {}: {}", target.path, e));
-            continue;
-        }
-        
-        // Copy package.json
-        let target_package_json = target_path.join("package.json");
-        if let Err(e) = std::fs::copy(&package_json_path, &target_package_json) {
-            results.push(format!("❌ Failed to copy package.json to {}: {}", target.path, e));
-            continue;
-        }
-        
-        // Update version in target's package.json
-        if let Err(e) = update_package_version(&target.path, &new_version) {
-            results.push(format!("❌ Failed to update version in {}: {}", target.path, e));
-            continue;
-        }
-        
-        results.push(format!("✅ Updated {} (v{} → v{})", target.path, current_version, new_version));
-    }
+    file.map(|f| f.path().to_string_lossy().to_string())
+}
+
+pub async fn open_bundle_dialog() -> Option<String> {
+    let file = rfd::AsyncFileDialog::new()
+        .set_title("Import Projects")
+        .add_filter("Project bundle", &["json", "toml"])
+        .pick_file()
+        .await;
     
-    Ok(results.join("\n"))
+    file.map(|f| f.path().to_string_lossy().to_string())
 }
 
+
+
 /// Extract project name from target path
 /// For paths like "/Users/random/Documents/project/node_modules/@package/name"
 /// Returns "project"
@@ -682,244 +1637,2185 @@ pub fn extract_project_name(path: &str) -> String {
     path.to_string()
 }
 
-// Note: build_and_update_project_with_progress function removed as it's not currently used
-// The UI uses build_and_update_project_cancellable instead
+// Build environment
 
-// Build with cancellation support and PID tracking
-pub async fn build_and_update_project_cancellable(
-    project: &Project,
-    mut progress_signal: dioxus::prelude::Signal<String>,
-    mut process_handle: dioxus::prelude::Signal<Option<tokio::process::Child>>
-) -> Result<String, String> {
-    if project.selected_build_commands.is_empty() {
-        return Err("No build commands selected".to_string());
+pub const SECRET_MASK: &str = "********";
+
+/// Variables passed to a project's build commands
+pub struct BuildEnv {
+    pub vars: Vec<(String, String)>, // In the order they were set, later ones win
+    secrets: Vec<String>,            // Values to mask in the build output
+}
+
+impl BuildEnv {
+    fn set(&mut self, key: String, value: String, secret: bool) {
+        self.vars.retain(|(existing, _)| existing != &key);
+        if secret && !value.is_empty() {
+            self.secrets.push(value.clone());
+        }
+        self.vars.push((key, value));
     }
     
-    let active_targets: Vec<_> = project.target_paths.iter()
-        .filter(|p| p.is_active)
-        .collect();
-    
-    if active_targets.is_empty() {
-        return Err("No active target paths".to_string());
+    /// "KEY=value" for every variable, with secret values masked
+    pub fn display(&self) -> Vec<String> {
+        self.vars.iter()
+            .map(|(key, value)| format!("{}={}", key, self.mask(value)))
+            .collect()
     }
     
-    let project_path = std::path::Path::new(&project.path);
-    let package_json_path = project_path.join("package.json");
-    
-    // Check if package.json exists
-    if !package_json_path.exists() {
-        return Err("package.json not found in project directory".to_string());
+    /// Replace every secret value found in the text
+    pub fn mask(&self, text: &str) -> String {
+        self.secrets.iter()
+            .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), SECRET_MASK))
     }
-    
-    let mut results = Vec::new();
-    
-    // Step 1: Execute build commands using bash script with cancellation support
-    results.push(format!("🚀 Executing {} build commands in order...", project.selected_build_commands.len()));
-    progress_signal.set("Creating build script...".to_string());
-    
-    // Create and execute bash script with all commands
-    match create_build_script(&project.selected_build_commands, &project.path) {
-        Ok(script_path) => {
-            progress_signal.set("Executing build commands...".to_string());
-            
-            // Execute the script as a cancellable process with process group - cross-platform
-            #[cfg(windows)]
-            let mut cmd = {
-                let mut c = tokio::process::Command::new("cmd");
-                c.args(["/C", &script_path]);
-                c
-            };
-            
-            #[cfg(unix)]
-            let mut cmd = {
-                let mut c = tokio::process::Command::new("bash");
-                c.arg(&script_path);
-                c
+}
+
+// A copy of the project with secret variable values masked, for anything
+// printed or handed to someone else
+pub fn mask_secret_env(project: &Project) -> Project {
+    let mut masked = project.clone();
+    for var in masked.env.iter_mut().filter(|var| var.secret) {
+        var.value = SECRET_MASK.to_string();
+    }
+    masked
+}
+
+pub fn validate_env_key(key: &str) -> Result<(), String> {
+    let mut chars = key.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid variable name (letters, digits and _, not starting with a digit)", key))
+    }
+}
+
+// Parse KEY=VALUE lines of a .env file; blank lines, # comments and an `export ` prefix
+// are allowed, values may be single quoted (literal) or double quoted (\n, \" and \\ escapes)
+pub fn parse_env_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected KEY=VALUE", index + 1));
+        };
+        let key = key.trim();
+        validate_env_key(key).map_err(|e| format!("line {}: {}", index + 1, e))?;
+        
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            let Some(end) = quoted.rfind('"') else {
+                return Err(format!("line {}: missing closing quote", index + 1));
             };
-            
-            cmd.current_dir(&project.path);
-            
-            // Set process group for better process tree management
-            #[cfg(unix)]
-            {
-                #[allow(unused_imports)]
-                use std::os::unix::process::CommandExt;
-                cmd.process_group(0);
+            let mut unescaped = String::new();
+            let mut chars = quoted[..end].chars();
+            while let Some(c) = chars.next() {
+                match (c, chars.clone().next()) {
+                    ('\\', Some('n')) => { chars.next(); unescaped.push('\n'); }
+                    ('\\', Some(escaped @ ('"' | '\\'))) => { chars.next(); unescaped.push(escaped); }
+                    _ => unescaped.push(c),
+                }
             }
-            
-            // On Windows, create a new process group
-            #[cfg(windows)]
-            {
-                use std::os::windows::process::CommandExt;
-                cmd.creation_flags(0x00000200); // CREATE_NEW_PROCESS_GROUP
+            unescaped
+        } else if let Some(quoted) = value.strip_prefix('\'') {
+            match quoted.rfind('\'') {
+                Some(end) => quoted[..end].to_string(),
+                None => return Err(format!("line {}: missing closing quote", index + 1)),
+            }
+        } else {
+            // Unquoted values end at a comment
+            value.split(" #").next().unwrap_or_default().trim_end().to_string()
+        };
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+pub fn env_file_path(project: &Project, file: &EnvFile) -> std::path::PathBuf {
+    std::path::Path::new(&project.path).join(&file.path)
+}
+
+// Collect the variables from the project's .env files and then its own variables
+// A listed .env file that is missing or malformed fails the build rather than
+// running it with part of its environment
+pub fn resolve_build_env(project: &Project) -> Result<BuildEnv, String> {
+    let mut env = BuildEnv { vars: Vec::new(), secrets: Vec::new() };
+    
+    for file in &project.env_files {
+        let path = env_file_path(project, file);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read env file {}: {}", path.display(), e))?;
+        let vars = parse_env_file(&content)
+            .map_err(|e| format!("Invalid env file {}: {}", path.display(), e))?;
+        for (key, value) in vars {
+            env.set(key, value, file.secret);
+        }
+    }
+    
+    for var in &project.env {
+        validate_env_key(&var.key)?;
+        env.set(var.key.clone(), var.value.clone(), var.secret);
+    }
+    
+    // Longest first, so a secret containing another is masked whole
+    env.secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    Ok(env)
+}
+
+// Build reporting
+
+/// Receives progress, output and the running process of a build
+/// Implemented by the GUI (signals) and the CLI (terminal)
+pub trait BuildReporter {
+    fn progress(&mut self, message: &str);
+    fn log(&mut self, line: BuildLogLine);
+    /// Slot holding the running build process; emptying it cancels the build
+    fn store_process(&mut self, child: Option<tokio::process::Child>);
+    fn take_process(&mut self) -> Option<tokio::process::Child>;
+}
+
+/// Running build process shared with whoever may cancel it
+pub type ProcessSlot = std::sync::Arc<std::sync::Mutex<Option<tokio::process::Child>>>;
+
+/// Streams build output to the terminal as it arrives
+#[derive(Default)]
+pub struct TerminalReporter {
+    process: ProcessSlot,
+    stderr_only: bool, // Keep stdout free for machine-readable output
+}
+
+impl TerminalReporter {
+    /// Reporter that writes everything to stderr
+    pub fn stderr_only() -> Self {
+        Self { stderr_only: true, ..Self::default() }
+    }
+    
+    /// Handle for cancelling the build from another task with `cancel_process_slot`
+    pub fn process_slot(&self) -> ProcessSlot {
+        self.process.clone()
+    }
+}
+
+impl BuildReporter for TerminalReporter {
+    fn progress(&mut self, message: &str) {
+        if self.stderr_only {
+            eprintln!("▶ {}", message);
+        } else {
+            println!("▶ {}", message);
+        }
+    }
+
+    fn log(&mut self, line: BuildLogLine) {
+        match line.stream {
+            LogStream::Stderr => eprintln!("{}", line.text),
+            _ if self.stderr_only => eprintln!("{}", line.text),
+            _ => println!("{}", line.text),
+        }
+    }
+
+    fn store_process(&mut self, child: Option<tokio::process::Child>) {
+        if let Ok(mut slot) = self.process.lock() {
+            *slot = child;
+        }
+    }
+
+    fn take_process(&mut self) -> Option<tokio::process::Child> {
+        self.process.lock().ok().and_then(|mut slot| slot.take())
+    }
+}
+
+/// Feeds build progress and output into the GUI signals
+pub struct SignalReporter {
+    pub progress: dioxus::prelude::Signal<String>,
+    pub log: dioxus::prelude::Signal<Vec<BuildLogLine>>,
+    pub process: dioxus::prelude::Signal<Option<tokio::process::Child>>,
+}
+
+impl BuildReporter for SignalReporter {
+    fn progress(&mut self, message: &str) {
+        self.progress.set(message.to_string());
+        self.log.write().push(BuildLogLine::new(LogStream::Info, message));
+    }
+
+    fn log(&mut self, line: BuildLogLine) {
+        self.log.write().push(line);
+    }
+
+    fn store_process(&mut self, child: Option<tokio::process::Child>) {
+        self.process.set(child);
+    }
+
+    fn take_process(&mut self) -> Option<tokio::process::Child> {
+        self.process.take()
+    }
+}
+
+// Number of stderr lines kept for the error message of a failed build
+const STDERR_TAIL_LINES: usize = 20;
+
+/// One build step, ready to spawn
+struct StepCommand {
+    program: String,
+    args: Vec<String>,
+    dir: String,
+}
+
+// A bare program name the way a Windows shell finds it: the first PATH folder holding
+// it with one of the PATHEXT extensions, e.g. npx -> C:\Program Files\nodejs\npx.cmd
+#[cfg(windows)]
+fn resolve_program(program: &str) -> String {
+    if program.contains(['/', '\\']) || std::path::Path::new(program).extension().is_some() {
+        return program.to_string();
+    }
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    for dir in std::env::split_paths(&build_path_var()) {
+        for extension in extensions.split(';').filter(|e| !e.is_empty()) {
+            let candidate = dir.join(format!("{}{}", program, extension));
+            if candidate.is_file() {
+                return candidate.to_string_lossy().to_string();
+            }
+        }
+    }
+    program.to_string()
+}
+
+// Unix looks programs up in PATH itself
+#[cfg(unix)]
+fn resolve_program(program: &str) -> String {
+    program.to_string()
+}
+
+// Scripts run where the package manager expects them (the workspace root for
+// workspace-scoped packages); commands and shell snippets run in the project folder
+fn step_command(project: &Project, pm: PackageManager, step: &BuildStep) -> StepCommand {
+    match &step.kind {
+        StepKind::Script { name, args } => {
+            let mut run_args = command_args(project, pm, name);
+            run_args.extend(pm.script_extra_args(args));
+            StepCommand {
+                program: package_manager_program(pm),
+                args: run_args,
+                dir: command_dir(project).to_string(),
+            }
+        }
+        StepKind::Command { program, args } => {
+            // A relative path like ./scripts/gen-icons.sh is relative to the project,
+            // a bare name like npx is looked up in PATH
+            let program = if program.contains(['/', '\\']) && std::path::Path::new(program).is_relative() {
+                std::path::Path::new(&project.path).join(program).to_string_lossy().to_string()
+            } else {
+                program.clone()
+            };
+            StepCommand { program, args: args.clone(), dir: project.path.clone() }
+        }
+        StepKind::Shell { script } => {
+            #[cfg(windows)]
+            let (program, flag) = ("cmd", "/C");
+            #[cfg(unix)]
+            let (program, flag) = ("sh", "-c");
+            StepCommand {
+                program: program.to_string(),
+                args: vec![flag.to_string(), script.clone()],
+                dir: project.path.clone(),
+            }
+        }
+    }
+}
+
+// Spawn one build command with piped output, streaming each line into a channel
+fn spawn_build_step(
+    command: &StepCommand,
+    env: &BuildEnv,
+) -> Result<(tokio::process::Child, tokio::sync::mpsc::UnboundedReceiver<BuildLogLine>), String> {
+    use tokio::io::AsyncBufReadExt;
+    
+    let mut cmd = tokio::process::Command::new(resolve_program(&command.program));
+    cmd.args(&command.args)
+        .current_dir(&command.dir)
+        .env("PATH", build_path_var())
+        .envs(env.vars.iter().map(|(key, value)| (key, value)))
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    
+    // Set process group for better process tree management
+    #[cfg(unix)]
+    {
+        #[allow(unused_imports)]
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    
+    // On Windows, create a new process group
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x00000200); // CREATE_NEW_PROCESS_GROUP
+    }
+    
+    let mut child = cmd.spawn()
+        .map_err(|e| format!("Failed to start {}: {}", command.program, e))?;
+    
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    
+    if let Some(stdout) = child.stdout.take() {
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            let mut lines = tokio::io::BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let _ = sender.send(BuildLogLine::new(LogStream::Stdout, line));
+            }
+        });
+    }
+    
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(async move {
+            let mut lines = tokio::io::BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let _ = sender.send(BuildLogLine::new(LogStream::Stderr, line));
+            }
+        });
+    }
+    
+    Ok((child, receiver))
+}
+
+// Run one build command to completion, forwarding its output tagged with the step
+// Returns the exit status, an Interrupted error when the build was cancelled, or a
+// TimedOut error when the deadline passed (the process tree is killed first)
+async fn run_build_step(
+    step: usize,
+    command: &StepCommand,
+    env: &BuildEnv,
+    deadline: Option<&(std::time::Instant, TimeoutHit)>,
+    reporter: &mut impl BuildReporter,
+    stderr_tail: &mut std::collections::VecDeque<String>,
+) -> std::io::Result<std::process::ExitStatus> {
+    let (child, mut receiver) = spawn_build_step(command, env)
+        .map_err(std::io::Error::other)?;
+    
+    // Store the process handle for potential cancellation
+    reporter.store_process(Some(child));
+    
+    // Wait for the process to complete while keeping handle available for cancellation
+    let status = loop {
+        while let Ok(line) = receiver.try_recv() {
+            forward_log_line(step, line, env, reporter, stderr_tail);
+        }
+        
+        if let Some((_, hit)) = deadline.filter(|(at, _)| std::time::Instant::now() >= *at) {
+            reporter.log(BuildLogLine::new(LogStream::Info, format!("⏱️ {}, stopping the build", hit)));
+            if let Some(child) = reporter.take_process() {
+                if let Err(e) = kill_build_child(child).await {
+                    eprintln!("Failed to stop timed out build: {}", e);
+                }
+            }
+            break Err(std::io::Error::new(std::io::ErrorKind::TimedOut, hit.to_string()));
+        }
+        
+        match reporter.take_process() {
+            // Process was cancelled or removed
+            None => break Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Process was cancelled")),
+            Some(mut child_process) => match child_process.try_wait() {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) => {
+                    // Process still running, put it back
+                    reporter.store_process(Some(child_process));
+                }
+                Err(e) => break Err(e),
+            },
+        }
+        
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    };
+    
+    // Clear the process handle after completion
+    reporter.store_process(None);
+    
+    // Drain what is left in the pipes; a detached grandchild could keep them
+    // open forever, so give up after a short grace period
+    let _ = tokio::time::timeout(tokio::time::Duration::from_secs(2), async {
+        while let Some(line) = receiver.recv().await {
+            forward_log_line(step, line, env, reporter, stderr_tail);
+        }
+    }).await;
+    
+    status
+}
+
+// Pass a line on to the reporter with secrets masked, remembering the last stderr
+// lines for the error message
+fn forward_log_line(
+    step: usize,
+    mut line: BuildLogLine,
+    env: &BuildEnv,
+    reporter: &mut impl BuildReporter,
+    stderr_tail: &mut std::collections::VecDeque<String>,
+) {
+    line.text = env.mask(&line.text);
+    line.step = Some(step);
+    if line.stream == LogStream::Stderr {
+        if stderr_tail.len() == STDERR_TAIL_LINES {
+            stderr_tail.pop_front();
+        }
+        stderr_tail.push_back(line.text.clone());
+    }
+    reporter.log(line);
+}
+
+// Main build and update logic
+pub async fn build_and_update_project(project: &Project) -> Result<String, String> {
+    run_build_and_update(project, &mut TerminalReporter::default(), true).await.0
+}
+
+// Same as build_and_update_project, reporting to the given terminal reporter so
+// the caller can cancel the build through its process slot
+// Also returns the history record when the build got past planning
+pub async fn build_and_update_project_with(project: &Project, reporter: &mut TerminalReporter) -> (Result<String, String>, Option<BuildRecord>) {
+    run_build_and_update(project, reporter, true).await
+}
+
+// Build one project of a cascade; see plan_cascade for the order
+pub async fn build_cascade_step(project: &Project, reporter: &mut impl BuildReporter) -> (Result<String, String>, Option<BuildRecord>) {
+    if let Some(reason) = cascade_skip_reason(project) {
+        return (Ok(format!("⏭️ {}: {}", project.name, reason)), None);
+    }
+    run_build_and_update(project, reporter, false).await
+}
+
+// A project in a cascade with no build steps (e.g. an app that only receives the
+// package) has nothing to build; it is passed over instead of failing the cascade
+pub fn cascade_skip_reason(project: &Project) -> Option<String> {
+    project.build_steps.is_empty().then(|| "no build steps, nothing to build".to_string())
+}
+
+// Build with cancellation support, PID tracking and live output
+pub async fn build_and_update_project_cancellable(
+    project: &Project,
+    progress_signal: dioxus::prelude::Signal<String>,
+    log_signal: dioxus::prelude::Signal<Vec<BuildLogLine>>,
+    process_handle: dioxus::prelude::Signal<Option<tokio::process::Child>>
+) -> Result<String, String> {
+    let mut reporter = SignalReporter {
+        progress: progress_signal,
+        log: log_signal,
+        process: process_handle,
+    };
+    run_build_and_update(project, &mut reporter, true).await.0
+}
+
+/// Wraps a reporter and keeps every log line for the build history
+struct RecordingReporter<'a, R: BuildReporter> {
+    inner: &'a mut R,
+    lines: Vec<BuildLogLine>,
+}
+
+impl<R: BuildReporter> BuildReporter for RecordingReporter<'_, R> {
+    fn progress(&mut self, message: &str) {
+        self.lines.push(BuildLogLine::new(LogStream::Info, message));
+        self.inner.progress(message);
+    }
+
+    fn log(&mut self, line: BuildLogLine) {
+        self.lines.push(line.clone());
+        self.inner.log(line);
+    }
+
+    fn store_process(&mut self, child: Option<tokio::process::Child>) {
+        self.inner.store_process(child);
+    }
+
+    fn take_process(&mut self) -> Option<tokio::process::Child> {
+        self.inner.take_process()
+    }
+}
+
+// Target validation
+
+// Everything a target must pass before a build writes into it
+// Forced targets skip the package checks but still have to be writable folders
+pub fn validate_target(project: &Project, target_path: &str, forced: bool) -> Result<(), String> {
+    check_target_access(target_path)?;
+    check_target_location(project, target_path)?;
+    if !forced {
+        check_target_package(project, target_path)?;
+    }
+    Ok(())
+}
+
+// The target is an existing folder that both it and its parent (where updates are staged) can be written to
+pub fn check_target_access(target_path: &str) -> Result<(), String> {
+    let path = std::path::Path::new(target_path);
+    if !path.exists() {
+        return Err(format!("{} does not exist", target_path));
+    }
+    if !path.is_dir() {
+        return Err(format!("{} is not a folder", target_path));
+    }
+    
+    // Checked without writing anything so that dry runs stay side-effect free
+    let parent = path.parent().unwrap_or(path);
+    for dir in [path, parent] {
+        std::fs::metadata(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        if !is_writable_dir(dir) {
+            return Err(format!("{} is not writable", dir.display()));
+        }
+    }
+    Ok(())
+}
+
+// Whether the current user may create and remove entries in `dir`
+#[cfg(unix)]
+fn is_writable_dir(dir: &std::path::Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    let Ok(path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else { return false };
+    // SAFETY: `path` is a valid NUL-terminated string that outlives the call
+    unsafe { libc::access(path.as_ptr(), libc::W_OK | libc::X_OK) == 0 }
+}
+
+// Windows only exposes the read-only attribute without going through ACLs
+#[cfg(not(unix))]
+fn is_writable_dir(dir: &std::path::Path) -> bool {
+    std::fs::metadata(dir).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+// The target lies outside the project, so a build never syncs into its own tree
+// Applies to forced targets too
+pub fn check_target_location(project: &Project, target_path: &str) -> Result<(), String> {
+    let path = std::path::Path::new(target_path);
+    let source = std::fs::canonicalize(&project.path).unwrap_or_else(|_| std::path::PathBuf::from(&project.path));
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if resolved.starts_with(&source) {
+        return Err(format!("{} is inside the project itself", target_path));
+    }
+    Ok(())
+}
+
+// The target holds the project's package
+// This is the check a forced target skips
+pub fn check_target_package(project: &Project, target_path: &str) -> Result<(), String> {
+    // Without a source package name there is nothing to compare against
+    let Some(expected) = get_package_name(&project.path) else { return Ok(()) };
+    match get_package_name(target_path) {
+        Some(name) if name == expected => Ok(()),
+        Some(name) => Err(format!("{} holds package '{}', not '{}'", target_path, name, expected)),
+        None => Err(format!("{} has no package.json naming '{}'", target_path, expected)),
+    }
+}
+
+// Build planning (dry run)
+
+// Resolve what a build would run and which targets it would update
+// Cheap enough to run before every build; file changes are left empty
+pub fn plan_build(project: &Project) -> Result<BuildPlan, String> {
+    plan_build_with(project, true)
+}
+
+pub fn plan_cascade_step(project: &Project) -> Result<BuildPlan, String> {
+    plan_build_with(project, false)
+}
+
+// Projects further down a cascade may have no targets (e.g. the consuming app);
+// they still run their commands
+fn plan_build_with(project: &Project, require_targets: bool) -> Result<BuildPlan, String> {
+    if project.build_steps.is_empty() {
+        return Err("No build commands selected".to_string());
+    }
+    
+    let active_targets: Vec<_> = project.target_paths.iter()
+        .filter(|p| p.is_active)
+        .collect();
+    
+    if require_targets && active_targets.is_empty() {
+        return Err("No active target paths".to_string());
+    }
+    
+    let project_path = std::path::Path::new(&project.path);
+    
+    // Check if package.json exists
+    if !project_path.join("package.json").exists() {
+        return Err("package.json not found in project directory".to_string());
+    }
+    
+    let package_manager = resolve_package_manager(project);
+    let output_dirs = resolve_output_dirs(project);
+    let env = resolve_build_env(project)?;
+    let mut warnings = Vec::new();
+    
+    let stale = stale_build_steps(project, &parse_package_json(&project.path));
+    if !stale.is_empty() {
+        warnings.push(format!("Selected scripts no longer in package.json: {}", stale.join(", ")));
+    }
+    
+    let missing_outputs: Vec<&str> = output_dirs.iter()
+        .filter(|dir| !project_path.join(&dir.source).exists())
+        .map(|dir| dir.source.as_str())
+        .collect();
+    if !missing_outputs.is_empty() {
+        warnings.push(format!("Output folders not built yet: {}", missing_outputs.join(", ")));
+    }
+    
+    let targets = active_targets.iter()
+        .map(|target| plan_target(project, target))
+        .collect();
+    
+    Ok(BuildPlan {
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        package_manager,
+        // Steps can spell out a secret; labels end up in the log and history
+        commands: project.build_steps.iter().map(|step| env.mask(&step.kind.to_string())).collect(),
+        command_lines: project.build_steps.iter()
+            .map(|step| env.mask(&step_command_line(project, package_manager, step)))
+            .collect(),
+        command_timeouts: project.build_steps.iter().map(|step| step.timeout_secs).collect(),
+        build_timeout_secs: project.build_timeout_secs,
+        output_dirs,
+        env: env.display(),
+        targets,
+        warnings,
+    })
+}
+
+fn plan_target(project: &Project, target: &TargetPath) -> TargetPlan {
+    let mut plan = TargetPlan {
+        target_id: target.id.clone(),
+        path: target.path.clone(),
+        version_bump: resolve_version_bump(project, target),
+        current_version: get_package_version(&target.path),
+        new_version: None,
+        file_changes: Vec::new(),
+        error: None,
+    };
+    
+    match plan_target_version(project, target) {
+        Ok((current_version, new_version)) => {
+            plan.current_version = Some(current_version);
+            plan.new_version = Some(new_version);
+        }
+        Err(e) => plan.error = Some(e),
+    }
+    
+    // A target that fails validation is skipped, whatever its version
+    if let Err(e) = validate_target(project, &target.path, target.forced) {
+        plan.error = Some(blocked_target_message(&e, target.forced));
+    } else if let Err(e) = recover_interrupted_updates(std::path::Path::new(&target.path)) {
+        plan.error = Some(format!("Failed to recover interrupted update: {}", e));
+    }
+    
+    plan
+}
+
+fn blocked_target_message(error: &str, forced: bool) -> String {
+    if forced {
+        format!("Blocked: {}", error)
+    } else {
+        format!("Blocked: {} (mark the target as forced to update it anyway)", error)
+    }
+}
+
+// Full dry run: the plan plus the files each target would gain, change or lose
+// File changes compare the current build output with each target, so they reflect
+// the last build rather than the one about to run
+pub fn preview_build(project: &Project) -> Result<BuildPlan, String> {
+    preview_build_with(project, true)
+}
+
+// Dry run of one project of a cascade, see plan_build_with
+pub fn preview_cascade_step(project: &Project) -> Result<BuildPlan, String> {
+    preview_build_with(project, false)
+}
+
+fn preview_build_with(project: &Project, require_targets: bool) -> Result<BuildPlan, String> {
+    let mut plan = plan_build_with(project, require_targets)?;
+    let project_path = std::path::Path::new(&project.path);
+    
+    for target_plan in plan.targets.iter_mut() {
+        let target_path = std::path::Path::new(&target_plan.path);
+        for dir in &plan.output_dirs {
+            let source = project_path.join(&dir.source);
+            if !source.exists() {
+                continue;
+            }
+            match diff_directories(&source, &target_path.join(&dir.destination)) {
+                Ok(changes) => target_plan.file_changes.extend(changes.into_iter().map(|mut change| {
+                    change.path = format!("{}/{}", dir.destination, change.path);
+                    change
+                })),
+                Err(e) => {
+                    target_plan.error.get_or_insert(e);
+                }
+            }
+        }
+        
+        // package.json is always replaced by the source one with the new version
+        target_plan.file_changes.push(FileChange {
+            path: "package.json".to_string(),
+            kind: if target_path.join("package.json").exists() { FileChangeKind::Changed } else { FileChangeKind::Added },
+        });
+    }
+    
+    Ok(plan)
+}
+
+// Compare two directory trees; paths are relative and use "/" separators
+// Files with the same size and modification time are treated as unchanged,
+// otherwise sizes and then contents are compared
+pub fn diff_directories(src: &std::path::Path, dst: &std::path::Path) -> Result<Vec<FileChange>, String> {
+    let src_files = list_files_recursive(src)?;
+    let dst_files = if dst.exists() { list_files_recursive(dst)? } else { Vec::new() };
+    let mut changes = Vec::new();
+    
+    // Both lists are sorted, so one pass over them pairs up the files
+    let mut src_iter = src_files.into_iter().peekable();
+    let mut dst_iter = dst_files.into_iter().peekable();
+    loop {
+        let order = match (src_iter.peek(), dst_iter.peek()) {
+            (None, None) => break,
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (Some(src_file), Some(dst_file)) => src_file.cmp(dst_file),
+        };
+        match order {
+            std::cmp::Ordering::Less => {
+                let path = src_iter.next().expect("peeked");
+                changes.push(FileChange { path, kind: FileChangeKind::Added });
+            }
+            std::cmp::Ordering::Greater => {
+                let path = dst_iter.next().expect("peeked");
+                changes.push(FileChange { path, kind: FileChangeKind::Removed });
+            }
+            std::cmp::Ordering::Equal => {
+                let path = src_iter.next().expect("peeked");
+                dst_iter.next();
+                if !files_identical(&src.join(&path), &dst.join(&path))? {
+                    changes.push(FileChange { path, kind: FileChangeKind::Changed });
+                }
+            }
+        }
+    }
+    Ok(changes)
+}
+
+// Relative paths of every file below `root`, sorted
+fn list_files_recursive(root: &std::path::Path) -> Result<Vec<String>, String> {
+    fn walk(root: &std::path::Path, dir: &std::path::Path, files: &mut Vec<String>) -> Result<(), String> {
+        for entry in std::fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))? {
+            let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                let parts: Vec<String> = relative.components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                files.push(parts.join("/"));
+            }
+        }
+        Ok(())
+    }
+    
+    let mut files = Vec::new();
+    walk(root, root, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn files_identical(a: &std::path::Path, b: &std::path::Path) -> Result<bool, String> {
+    let a_meta = std::fs::metadata(a).map_err(|e| format!("Failed to read {:?}: {}", a, e))?;
+    let b_meta = std::fs::metadata(b).map_err(|e| format!("Failed to read {:?}: {}", b, e))?;
+    if a_meta.len() != b_meta.len() {
+        return Ok(false);
+    }
+    if let (Ok(a_modified), Ok(b_modified)) = (a_meta.modified(), b_meta.modified()) {
+        if a_modified == b_modified {
+            return Ok(true);
+        }
+    }
+    let a_content = std::fs::read(a).map_err(|e| format!("Failed to read {:?}: {}", a, e))?;
+    let b_content = std::fs::read(b).map_err(|e| format!("Failed to read {:?}: {}", b, e))?;
+    Ok(a_content == b_content)
+}
+
+// Returns the build result and, once the build got past planning, its history record
+async fn run_build_and_update(project: &Project, reporter: &mut impl BuildReporter, require_targets: bool) -> (Result<String, String>, Option<BuildRecord>) {
+    let plan = match plan_build_with(project, require_targets) {
+        Ok(plan) => plan,
+        Err(e) => return (Err(e), None),
+    };
+    
+    let started_at = chrono::Local::now();
+    let mut record = BuildRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_id: project.id.clone(),
+        project_name: project.name.clone(),
+        started_at,
+        finished_at: started_at,
+        duration_ms: 0,
+        package_manager: plan.package_manager,
+        commands: plan.commands.clone(),
+        status: BuildStatus::Succeeded,
+        exit_code: None,
+        error: None,
+        targets: Vec::new(),
+        log: Vec::new(),
+        command_outcomes: plan.commands.iter()
+            .map(|command| CommandOutcome {
+                command: command.clone(),
+                status: CommandStatus::NotRun,
+                exit_code: None,
+                duration_ms: 0,
+            })
+            .collect(),
+        timeout: None,
+    };
+    
+    let mut recorder = RecordingReporter { inner: reporter, lines: Vec::new() };
+    let result = execute_build(project, &plan, &mut recorder, &mut record).await;
+    
+    record.finished_at = chrono::Local::now();
+    record.duration_ms = (record.finished_at - record.started_at).num_milliseconds().max(0) as u64;
+    record.log = recorder.lines;
+    match &result {
+        Ok(_) => {
+            if record.targets.iter().any(|t| t.error.is_some()) {
+                record.status = BuildStatus::PartiallyFailed;
+            }
+        }
+        Err(e) => {
+            if record.status != BuildStatus::Cancelled && record.status != BuildStatus::TimedOut {
+                record.status = BuildStatus::Failed;
+            }
+            record.error = Some(e.clone());
+        }
+    }
+    
+    if let Err(e) = save_build_record(&record) {
+        eprintln!("Failed to save build history: {}", e);
+    }
+    
+    (result, Some(record))
+}
+
+// Carry out a plan: run the commands, then update every planned target
+async fn execute_build(project: &Project, plan: &BuildPlan, reporter: &mut impl BuildReporter, record: &mut BuildRecord) -> Result<String, String> {
+    let active_targets: Vec<&TargetPath> = plan.targets.iter()
+        .filter_map(|t| project.target_paths.iter().find(|p| p.id == t.target_id))
+        .collect();
+    
+    let project_path = std::path::Path::new(&project.path);
+    let package_json_path = project_path.join("package.json");
+    
+    let package_manager = plan.package_manager;
+    let mut results = Vec::new();
+    
+    // Step 1: Run each build command as its own process, stopping at the first that fails
+    results.push(format!("🚀 Executing {} build commands in order with {}...", plan.commands.len(), package_manager));
+    let env = resolve_build_env(project).map_err(|e| format!("❌ {}", e))?;
+    remove_leftover_build_script(command_dir(project));
+    
+    let build_started = std::time::Instant::now();
+    let step_count = plan.commands.len();
+    for (index, command) in plan.commands.iter().enumerate() {
+        reporter.progress(&format!("Step {}/{}: {}", index + 1, step_count, command));
+        reporter.log(BuildLogLine { step: Some(index), ..BuildLogLine::new(LogStream::Info, format!("$ {}", env.mask(&plan.command_lines[index]))) });
+        
+        // Whichever runs out first: this command's own timeout or the build's
+        let step_started = std::time::Instant::now();
+        let step_deadline = plan.command_timeouts.get(index).copied().flatten().map(|limit| {
+            (step_started + std::time::Duration::from_secs(limit), TimeoutHit { command: command.clone(), limit_secs: limit, whole_build: false })
+        });
+        let build_deadline = plan.build_timeout_secs.map(|limit| {
+            (build_started + std::time::Duration::from_secs(limit), TimeoutHit { command: command.clone(), limit_secs: limit, whole_build: true })
+        });
+        let deadline = [step_deadline, build_deadline].into_iter().flatten().min_by_key(|(at, _)| *at);
+        
+        let mut stderr_tail = std::collections::VecDeque::new();
+        let step = step_command(project, package_manager, &project.build_steps[index]);
+        let status = run_build_step(index, &step, &env, deadline.as_ref(), reporter, &mut stderr_tail).await;
+        
+        let outcome = &mut record.command_outcomes[index];
+        outcome.duration_ms = step_started.elapsed().as_millis() as u64;
+        match status {
+            Ok(status) if status.success() => {
+                outcome.status = CommandStatus::Succeeded;
+                outcome.exit_code = status.code();
+                record.exit_code = status.code();
+                results.push(format!("✅ {} ({})", command, format_duration_ms(outcome.duration_ms)));
+            }
+            Ok(status) => {
+                outcome.status = CommandStatus::Failed;
+                outcome.exit_code = status.code();
+                record.exit_code = status.code();
+                let exit = status.code()
+                    .map(|code| format!("exit code {}", code))
+                    .unwrap_or_else(|| "terminated by signal".to_string());
+                let tail: Vec<String> = stderr_tail.into_iter().collect();
+                return Err(format!("❌ Step {}/{} '{}' failed ({}):\n{}", index + 1, step_count, command, exit, tail.join("\n")));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                outcome.status = CommandStatus::TimedOut;
+                record.status = BuildStatus::TimedOut;
+                record.timeout = deadline.map(|(_, hit)| hit);
+                return Err(format!("⏱️ Timeout: {}", e));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+                outcome.status = CommandStatus::Cancelled;
+                record.status = BuildStatus::Cancelled;
+                return Err(format!("❌ Build cancelled during step {}/{} '{}'", index + 1, step_count, command));
+            }
+            Err(e) => {
+                outcome.status = CommandStatus::Failed;
+                return Err(format!("❌ Step {}/{} '{}' could not run: {}", index + 1, step_count, command, e));
+            }
+        }
+    }
+    
+    results.push("\n📦 Build commands completed successfully!".to_string());
+    
+    if active_targets.is_empty() {
+        results.push("ℹ️ No active targets, nothing to update".to_string());
+        return Ok(results.join("\n"));
+    }
+    
+    // Step 2: Check that every output directory exists after build
+    reporter.progress("Verifying build output...");
+    let output_dirs = &plan.output_dirs;
+    verify_output_dirs(project_path, output_dirs)?;
+    
+    results.push("\n📤 Updating target paths...".to_string());
+    
+    // Process each active target
+    for (index, target) in active_targets.iter().enumerate() {
+        reporter.progress(&format!("Updating target {} of {}: {}", index + 1, active_targets.len(), extract_project_name(&target.path)));
+        
+        let mut outcome = TargetOutcome {
+            target_id: target.id.clone(),
+            path: target.path.clone(),
+            old_version: get_package_version(&target.path),
+            new_version: None,
+            error: None,
+            backed_up: false,
+            files: SyncStats::default(),
+        };
+        
+        // Never write into a target that is not what it claims to be
+        if let Err(e) = validate_target(project, &target.path, target.forced) {
+            let message = blocked_target_message(&e, target.forced);
+            results.push(format!("🚫 {}", message));
+            outcome.error = Some(message);
+            record.targets.push(outcome);
+            continue;
+        }
+        
+        // A previous update may have been interrupted half way through the swap;
+        // the snapshot has to be taken of the target as it was before that update
+        if let Err(e) = recover_interrupted_updates(std::path::Path::new(&target.path)) {
+            let message = format!("Failed to recover interrupted update of {}: {}", target.path, e);
+            results.push(format!("❌ {}", message));
+            outcome.error = Some(message);
+            record.targets.push(outcome);
+            continue;
+        }
+        
+        // Never overwrite a target we could not snapshot
+        if let Err(e) = backup_target(project, target, &record.id, output_dirs) {
+            let message = format!("Failed to back up {}: {}", target.path, e);
+            results.push(format!("❌ {}", message));
+            outcome.error = Some(message);
+            record.targets.push(outcome);
+            continue;
+        }
+        outcome.backed_up = true;
+        
+        match update_target(project, target, &package_json_path, output_dirs) {
+            Ok((current_version, new_version, files)) => {
+                results.push(format!("✅ Updated {} (v{} → v{}, {}, {})", target.path, current_version, new_version, resolve_version_bump(project, target), files));
+                outcome.old_version = Some(current_version);
+                outcome.new_version = Some(new_version);
+                outcome.files = files;
+            }
+            Err(e) => {
+                results.push(format!("❌ {}", e));
+                outcome.error = Some(e);
+            }
+        }
+        record.targets.push(outcome);
+    }
+    
+    reporter.progress("Finalizing...");
+    prune_backups(&project.id, load_settings().backup_retention);
+    
+    Ok(results.join("\n"))
+}
+
+// Sync the build output and package.json into one target and set its new version
+// Only files that differ are staged next to the target and swapped in with renames,
+// so after a failure the target is left either fully old or fully new
+// Returns the target's previous and new version and the number of files touched
+fn update_target(
+    project: &Project,
+    target: &TargetPath,
+    package_json_path: &std::path::Path,
+    output_dirs: &[OutputDir],
+) -> Result<(String, String, SyncStats), String> {
+    let project_path = std::path::Path::new(&project.path);
+    let target_path = std::path::Path::new(&target.path);
+    
+    // Work out the new version from the target's current one
+    let (current_version, new_version) = plan_target_version(project, target)
+        .map_err(|e| format!("Failed to compute version for {}: {}", target.path, e))?;
+    
+    let staging_dir = create_staging_dir(target_path)
+        .map_err(|e| format!("Failed to prepare update of {}: {}", target.path, e))?;
+    
+    let staged = stage_target_update(project_path, target_path, package_json_path, output_dirs, &staging_dir, &new_version)
+        .map_err(|e| format!("Failed to stage update of {}: {}", target.path, e));
+    
+    let result = match staged {
+        Ok((entries, stats)) => commit_staged_update(target_path, &staging_dir, entries)
+            .map(|_| stats)
+            .map_err(|e| format!("Failed to update {}: {}", target.path, e)),
+        Err(e) => Err(e),
+    };
+    
+    // The staging folder only survives when an undo failed; it is then needed
+    // to finish the recovery on the next update
+    if !staging_dir.join(STAGING_JOURNAL).exists() {
+        let _ = std::fs::remove_dir_all(&staging_dir);
+    }
+    
+    // Removed files can leave empty folders behind in the target
+    if result.is_ok() {
+        for dir in output_dirs {
+            remove_empty_dirs(&target_path.join(&dir.destination));
+        }
+    }
+    
+    result.map(|stats| (current_version, new_version, stats))
+}
+
+// Atomic target updates
+
+const STAGING_MARKER: &str = ".lbm-staging-";
+const STAGING_JOURNAL: &str = "journal.json";
+
+/// Written before the first rename so an interrupted swap can be undone
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StagingJournal {
+    entries: Vec<StagedEntry>,
+    committed: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+struct StagedEntry {
+    path: String,  // Relative to the target, e.g. "dist/index.js" or "package.json"
+    had_old: bool, // The target had this entry before the update
+    #[serde(default)]
+    removed: bool, // The entry is deleted rather than replaced
+}
+
+// Staging folders are siblings of the target so renames stay on one filesystem
+// e.g. node_modules/@scope/.lib.lbm-staging-1a2b3c4d next to node_modules/@scope/lib
+fn staging_prefix(target_path: &std::path::Path) -> Result<(std::path::PathBuf, String), String> {
+    let parent = target_path.parent()
+        .ok_or_else(|| format!("{:?} has no parent directory", target_path))?;
+    let name = target_path.file_name()
+        .ok_or_else(|| format!("{:?} has no directory name", target_path))?
+        .to_string_lossy();
+    Ok((parent.to_path_buf(), format!(".{}{}", name, STAGING_MARKER)))
+}
+
+fn create_staging_dir(target_path: &std::path::Path) -> Result<std::path::PathBuf, String> {
+    let (parent, prefix) = staging_prefix(target_path)?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let staging_dir = parent.join(format!("{}{}", prefix, &id[..8]));
+    std::fs::create_dir_all(staging_dir.join("new"))
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    std::fs::create_dir_all(staging_dir.join("old"))
+        .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+    Ok(staging_dir)
+}
+
+// Copy the files that differ from the target and the versioned package.json into
+// `<staging>/new` and check them; files missing from the source are marked for removal
+fn stage_target_update(
+    project_path: &std::path::Path,
+    target_path: &std::path::Path,
+    package_json_path: &std::path::Path,
+    output_dirs: &[OutputDir],
+    staging_dir: &std::path::Path,
+    new_version: &str,
+) -> Result<(Vec<StagedEntry>, SyncStats), String> {
+    let new_dir = staging_dir.join("new");
+    let mut entries = Vec::new();
+    let mut stats = SyncStats::default();
+    
+    for dir in output_dirs {
+        let source = project_path.join(&dir.source);
+        if !source.exists() {
+            return Err(format!("Source directory does not exist: {:?}", source));
+        }
+        
+        let destination = target_path.join(&dir.destination);
+        let mut replaced_dirs = Vec::new();
+        for change in diff_directories(&source, &destination)? {
+            let path = format!("{}/{}", dir.destination, change.path);
+            match change.kind {
+                FileChangeKind::Added => stats.added += 1,
+                FileChangeKind::Changed => stats.updated += 1,
+                FileChangeKind::Removed => {
+                    stats.removed += 1;
+                    entries.push(StagedEntry { path, had_old: true, removed: true });
+                    continue;
+                }
             }
             
-            let child = cmd.spawn()
-                .map_err(|e| {
-                    let _ = std::fs::remove_file(&script_path);
-                    format!("❌ Failed to start build script: {}", e)
-                })?;
+            let source_file = source.join(&change.path);
+            let staged_file = new_dir.join(&path);
+            copy_file(&source_file, &staged_file)?;
             
-            // Store the process handle for potential cancellation
-            process_handle.set(Some(child));
+            // Check the copy is complete before it goes anywhere near the target
+            if file_len(&source_file)? != file_len(&staged_file)? {
+                return Err(format!("Staged copy of {} is incomplete", path));
+            }
             
-            // Wait for the process to complete while keeping handle available for cancellation
-            let output = loop {
-                // Check if we still have a process handle (not cancelled)
-                let has_process = process_handle.read().is_some();
-                if !has_process {
-                    // Process was cancelled
-                    break Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Process was cancelled"));
+            // A file taking the place of a folder moves the whole old folder aside
+            let replaces_dir = destination.join(&change.path).is_dir();
+            if replaces_dir {
+                replaced_dirs.push(format!("{}/", path));
+            }
+            entries.push(StagedEntry { path, had_old: change.kind == FileChangeKind::Changed || replaces_dir, removed: false });
+        }
+        entries.retain(|entry| !(entry.removed && replaced_dirs.iter().any(|dir| entry.path.starts_with(dir.as_str()))));
+    }
+    
+    // Removals go first, so a file that becomes a folder is out of the way before
+    // the files inside the new folder move in
+    entries.sort_by_key(|entry| !entry.removed);
+    
+    std::fs::copy(package_json_path, new_dir.join("package.json"))
+        .map_err(|e| format!("Failed to copy package.json: {}", e))?;
+    update_package_version(&new_dir.to_string_lossy(), new_version)?;
+    if get_package_version(&new_dir.to_string_lossy()).as_deref() != Some(new_version) {
+        return Err("Staged package.json does not contain the new version".to_string());
+    }
+    entries.push(StagedEntry {
+        path: "package.json".to_string(),
+        had_old: target_path.join("package.json").exists(),
+        removed: false,
+    });
+    
+    Ok((entries, stats))
+}
+
+// Swap the staged entries into the target: each old entry is moved into
+// `<staging>/old`, then the new one (if any) is moved into place
+fn commit_staged_update(target_path: &std::path::Path, staging_dir: &std::path::Path, entries: Vec<StagedEntry>) -> Result<(), String> {
+    let mut journal = StagingJournal { entries, committed: false };
+    write_staging_journal(staging_dir, &journal)?;
+    
+    let swap = || -> Result<(), String> {
+        for entry in &journal.entries {
+            let current = target_path.join(&entry.path);
+            if entry.had_old {
+                move_entry(&current, &staging_dir.join("old").join(&entry.path))?;
+            }
+            if !entry.removed {
+                move_entry(&staging_dir.join("new").join(&entry.path), &current)?;
+            }
+        }
+        Ok(())
+    };
+    
+    match swap() {
+        Ok(()) => {
+            journal.committed = true;
+            // The swap is complete; a failure here only leaves cleanup behind
+            let _ = write_staging_journal(staging_dir, &journal);
+            let _ = std::fs::remove_file(staging_dir.join(STAGING_JOURNAL));
+            Ok(())
+        }
+        Err(e) => {
+            undo_staged_update(target_path, staging_dir, &journal.entries)
+                .map_err(|undo_error| format!("{} (undo also failed: {}; it will be retried on the next update)", e, undo_error))?;
+            let _ = std::fs::remove_file(staging_dir.join(STAGING_JOURNAL));
+            Err(format!("{} (target left unchanged)", e))
+        }
+    }
+}
+
+// Put the old entries back, working out from the files on disk how far the swap got
+fn undo_staged_update(target_path: &std::path::Path, staging_dir: &std::path::Path, entries: &[StagedEntry]) -> Result<(), String> {
+    for entry in entries.iter().rev() {
+        let current = target_path.join(&entry.path);
+        let old = staging_dir.join("old").join(&entry.path);
+        let new = staging_dir.join("new").join(&entry.path);
+        
+        // The new entry made it into the target when it is no longer staged
+        let new_moved_in = !entry.removed && !new.exists() && current.exists();
+        
+        if entry.had_old {
+            if old.exists() {
+                // A file that became a folder leaves that folder behind, emptied by the
+                // entries undone before this one
+                if new_moved_in || (current.is_dir() && !old.is_dir()) {
+                    remove_entry(&current)?;
+                }
+                move_entry(&old, &current)?;
+            }
+        } else if new_moved_in {
+            remove_entry(&current)?;
+        }
+    }
+    Ok(())
+}
+
+// Finish or undo updates of this target that were interrupted (crash, kill, power loss)
+pub fn recover_interrupted_updates(target_path: &std::path::Path) -> Result<(), String> {
+    let (parent, prefix) = staging_prefix(target_path)?;
+    let entries = match std::fs::read_dir(&parent) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+    
+    for entry in entries.filter_map(|e| e.ok()) {
+        if !entry.file_name().to_string_lossy().starts_with(&prefix) {
+            continue;
+        }
+        let staging_dir = entry.path();
+        let journal = std::fs::read_to_string(staging_dir.join(STAGING_JOURNAL))
+            .ok()
+            .and_then(|content| serde_json::from_str::<StagingJournal>(&content).ok());
+        
+        // No journal means the swap never started: the target was not touched
+        if let Some(journal) = journal {
+            if !journal.committed {
+                eprintln!("↺ Undoing interrupted update of {:?}", target_path);
+                undo_staged_update(target_path, &staging_dir, &journal.entries)?;
+            }
+        }
+        std::fs::remove_dir_all(&staging_dir)
+            .map_err(|e| format!("Failed to remove staging directory {:?}: {}", staging_dir, e))?;
+    }
+    Ok(())
+}
+
+// Recover every active target of every project, e.g. after a crash mid-build
+pub fn recover_all_interrupted_updates() {
+    for project in load_projects() {
+        for target in project.target_paths.iter().filter(|t| t.is_active) {
+            if let Err(e) = recover_interrupted_updates(std::path::Path::new(&target.path)) {
+                eprintln!("Failed to recover interrupted update of {}: {}", target.path, e);
+            }
+        }
+    }
+}
+
+fn write_staging_journal(staging_dir: &std::path::Path, journal: &StagingJournal) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(journal)
+        .map_err(|e| format!("Failed to serialize staging journal: {}", e))?;
+    std::fs::write(staging_dir.join(STAGING_JOURNAL), content)
+        .map_err(|e| format!("Failed to write staging journal: {}", e))
+}
+
+fn move_entry(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    std::fs::rename(from, to)
+        .map_err(|e| format!("Failed to move {:?} to {:?}: {}", from, to, e))
+}
+
+fn remove_entry(path: &std::path::Path) -> Result<(), String> {
+    let result = if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+    result.map_err(|e| format!("Failed to remove {:?}: {}", path, e))
+}
+
+// Remove folders below `dir` (and `dir` itself) that no longer contain any file
+fn remove_empty_dirs(dir: &std::path::Path) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.path().is_dir() {
+                remove_empty_dirs(&entry.path());
+            }
+        }
+    }
+    // Fails, as intended, when the folder is not empty
+    let _ = std::fs::remove_dir(dir);
+}
+
+fn file_len(path: &std::path::Path) -> Result<u64, String> {
+    std::fs::metadata(path)
+        .map(|meta| meta.len())
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))
+}
+
+// Build history persistence
+
+pub fn get_history_dir(project_id: &str) -> std::path::PathBuf {
+    let mut path = get_data_dir();
+    path.push("history");
+    path.push(project_id);
+    path
+}
+
+// Each run is stored as its own file so a corrupt record never hides the others
+pub fn get_build_record_file(record: &BuildRecord) -> std::path::PathBuf {
+    get_history_dir(&record.project_id).join(format!(
+        "{}-{}.json",
+        record.started_at.format("%Y%m%dT%H%M%S"),
+        record.id
+    ))
+}
+
+pub fn save_build_record(record: &BuildRecord) -> Result<std::path::PathBuf, String> {
+    let dir = get_history_dir(&record.project_id);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create history directory: {}", e))?;
+    
+    let file_path = get_build_record_file(record);
+    let content = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize build record: {}", e))?;
+    std::fs::write(&file_path, content)
+        .map_err(|e| format!("Failed to write build record: {}", e))?;
+    
+    Ok(file_path)
+}
+
+// All recorded builds of a project, newest first
+pub fn load_build_history(project_id: &str) -> Vec<BuildRecord> {
+    let dir = get_history_dir(project_id);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    
+    let mut records: Vec<BuildRecord> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            match serde_json::from_str::<BuildRecord>(&content) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    eprintln!("Skipping unreadable build record {:?}: {}", path, e);
+                    None
                 }
-                
-                // Try to check if process is done
-                let mut process_done = false;
-                let mut process_result = None;
-                
-                if let Some(mut child_process) = process_handle.take() {
-                    match child_process.try_wait() {
-                        Ok(Some(_status)) => {
-                            // Process completed, get final output
-                            let result = child_process.wait_with_output().await;
-                            process_result = Some(result);
-                            process_done = true;
-                        }
-                        Ok(None) => {
-                            // Process still running, put it back
-                            process_handle.set(Some(child_process));
-                        }
-                        Err(e) => {
-                            // Error checking process status
-                            process_result = Some(Err(e));
-                            process_done = true;
-                        }
-                    }
-                } else {
-                    // Process was cancelled or removed
-                    break Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Process was cancelled"));
+            }
+        })
+        .collect();
+    
+    records.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+    records
+}
+
+// Find a record by id or by a unique id prefix (as printed by the CLI)
+pub fn find_build_record(project_id: &str, record_id: &str) -> Option<BuildRecord> {
+    let matches: Vec<BuildRecord> = load_build_history(project_id)
+        .into_iter()
+        .filter(|r| r.id.starts_with(record_id))
+        .collect();
+    
+    if matches.len() == 1 {
+        matches.into_iter().next()
+    } else {
+        None
+    }
+}
+
+// Filter used by both the history page and the CLI
+// `query` matches commands, target paths, versions, the error and the log
+pub fn filter_build_history(records: &[BuildRecord], status: Option<BuildStatus>, query: &str) -> Vec<BuildRecord> {
+    let query = query.trim().to_lowercase();
+    records.iter()
+        .filter(|r| status.is_none_or(|s| r.status == s))
+        .filter(|r| {
+            if query.is_empty() {
+                return true;
+            }
+            r.commands.iter().any(|c| c.to_lowercase().contains(&query))
+                || r.targets.iter().any(|t| {
+                    t.path.to_lowercase().contains(&query)
+                        || t.old_version.as_deref().is_some_and(|v| v.contains(&query))
+                        || t.new_version.as_deref().is_some_and(|v| v.contains(&query))
+                })
+                || r.error.as_deref().is_some_and(|e| e.to_lowercase().contains(&query))
+                || r.log.iter().any(|l| l.text.to_lowercase().contains(&query))
+        })
+        .cloned()
+        .collect()
+}
+
+// Target backups and rollback
+
+pub fn get_backups_dir(project_id: &str) -> std::path::PathBuf {
+    let mut path = get_data_dir();
+    path.push("backups");
+    path.push(project_id);
+    path
+}
+
+// Snapshots of one build live under a folder named after the build
+fn get_build_backup_dir(project_id: &str, build_id: &str) -> std::path::PathBuf {
+    get_backups_dir(project_id).join(build_id)
+}
+
+fn get_target_backup_dir(backup: &TargetBackup) -> std::path::PathBuf {
+    get_build_backup_dir(&backup.project_id, &backup.build_id).join(&backup.target_id)
+}
+
+// Snapshot the parts of a target a build is about to overwrite: the output files
+// that differ from the new build and its package.json; unchanged files are left out
+fn backup_target(
+    project: &Project,
+    target: &TargetPath,
+    build_id: &str,
+    output_dirs: &[OutputDir],
+) -> Result<TargetBackup, String> {
+    let target_path = std::path::Path::new(&target.path);
+    let mut backup = TargetBackup {
+        project_id: project.id.clone(),
+        build_id: build_id.to_string(),
+        target_id: target.id.clone(),
+        target_path: target.path.clone(),
+        created_at: chrono::Local::now(),
+        version: get_package_version(&target.path),
+        entries: Vec::new(),
+        missing_entries: Vec::new(),
+        had_package_json: target_path.join("package.json").exists(),
+        incremental: true,
+        saved_files: Vec::new(),
+        added_files: Vec::new(),
+    };
+    
+    let backup_dir = get_target_backup_dir(&backup);
+    let files_dir = backup_dir.join("files");
+    std::fs::create_dir_all(&files_dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    
+    let project_path = std::path::Path::new(&project.path);
+    for dir in output_dirs {
+        let destination = target_path.join(&dir.destination);
+        if !destination.exists() {
+            backup.missing_entries.push(dir.destination.clone());
+            continue;
+        }
+        backup.entries.push(dir.destination.clone());
+        
+        // A missing build output fails the update itself, which then changes nothing
+        let source = project_path.join(&dir.source);
+        if !source.exists() {
+            continue;
+        }
+        for change in diff_directories(&source, &destination)? {
+            let path = format!("{}/{}", dir.destination, change.path);
+            match change.kind {
+                FileChangeKind::Added => backup.added_files.push(path),
+                FileChangeKind::Changed | FileChangeKind::Removed => {
+                    copy_file(&target_path.join(&path), &files_dir.join(&path))?;
+                    backup.saved_files.push(path);
                 }
-                
-                if process_done {
-                    if let Some(result) = process_result {
-                        break result;
-                    }
-                } else {
-                    // Process still running, wait a bit and check again
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            }
+        }
+    }
+    
+    if backup.had_package_json {
+        std::fs::copy(target_path.join("package.json"), files_dir.join("package.json"))
+            .map_err(|e| format!("Failed to back up package.json: {}", e))?;
+    }
+    
+    let manifest = serde_json::to_string_pretty(&backup)
+        .map_err(|e| format!("Failed to serialize backup manifest: {}", e))?;
+    std::fs::write(backup_dir.join("backup.json"), manifest)
+        .map_err(|e| format!("Failed to write backup manifest: {}", e))?;
+    
+    Ok(backup)
+}
+
+// All target snapshots of a project, newest first
+pub fn list_backups(project_id: &str) -> Vec<TargetBackup> {
+    let mut backups = Vec::new();
+    let build_dirs = match std::fs::read_dir(get_backups_dir(project_id)) {
+        Ok(entries) => entries,
+        Err(_) => return backups,
+    };
+    
+    for build_dir in build_dirs.filter_map(|e| e.ok()) {
+        let target_dirs = match std::fs::read_dir(build_dir.path()) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for target_dir in target_dirs.filter_map(|e| e.ok()) {
+            let manifest = target_dir.path().join("backup.json");
+            if let Some(backup) = std::fs::read_to_string(&manifest)
+                .ok()
+                .and_then(|content| serde_json::from_str::<TargetBackup>(&content).ok())
+            {
+                backups.push(backup);
+            }
+        }
+    }
+    
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    backups
+}
+
+// Snapshots taken by one build, matched by build id or a unique prefix
+pub fn list_build_backups(project_id: &str, build_id: &str) -> Vec<TargetBackup> {
+    let backups = list_backups(project_id);
+    let mut build_ids: Vec<&str> = backups.iter()
+        .map(|b| b.build_id.as_str())
+        .filter(|id| id.starts_with(build_id))
+        .collect();
+    build_ids.sort();
+    build_ids.dedup();
+    if build_ids.len() != 1 {
+        return vec![];
+    }
+    let matched = build_ids[0].to_string();
+    backups.into_iter().filter(|b| b.build_id == matched).collect()
+}
+
+// Put a target back exactly as it was before the snapshot was taken
+pub fn rollback_target(backup: &TargetBackup) -> Result<String, String> {
+    let target_path = std::path::Path::new(&backup.target_path);
+    let files_dir = get_target_backup_dir(backup).join("files");
+    if !files_dir.exists() {
+        return Err(format!("Backup files for {} are missing", backup.target_path));
+    }
+    
+    // Leftovers of an interrupted swap would otherwise be put back over the restored files
+    recover_interrupted_updates(target_path)
+        .map_err(|e| format!("Failed to recover interrupted update of {}: {}", backup.target_path, e))?;
+    
+    // Restored like an update: staged next to the target and swapped in through the
+    // journal, so a rollback that fails halfway leaves the target as it was
+    let staging_dir = create_staging_dir(target_path)
+        .map_err(|e| format!("Failed to prepare rollback of {}: {}", backup.target_path, e))?;
+    
+    let result = stage_rollback(backup, target_path, &files_dir, &staging_dir)
+        .map_err(|e| format!("Failed to stage rollback of {}: {}", backup.target_path, e))
+        .and_then(|entries| commit_staged_update(target_path, &staging_dir, entries)
+            .map_err(|e| format!("Failed to restore {}: {}", backup.target_path, e)));
+    
+    // See update_target: the staging folder is kept only for an undo still to finish
+    if !staging_dir.join(STAGING_JOURNAL).exists() {
+        let _ = std::fs::remove_dir_all(&staging_dir);
+    }
+    result?;
+    
+    // Folders left empty by removed files go too, the destinations themselves stay
+    if backup.incremental {
+        for entry in &backup.entries {
+            remove_empty_dirs(&target_path.join(entry));
+            let _ = std::fs::create_dir_all(target_path.join(entry));
+        }
+    }
+    
+    let version = backup.version.as_deref()
+        .map(|v| format!("v{}", v))
+        .unwrap_or_else(|| "no version".to_string());
+    Ok(format!("↺ Restored {} to {} (backup from {})", backup.target_path, version, backup.created_at.format("%Y-%m-%d %H:%M:%S")))
+}
+
+// Copy what the backup restores into `<staging>/new` and list the entries to swap:
+// files the build added or created go, saved files and package.json come back
+fn stage_rollback(
+    backup: &TargetBackup,
+    target_path: &std::path::Path,
+    files_dir: &std::path::Path,
+    staging_dir: &std::path::Path,
+) -> Result<Vec<StagedEntry>, String> {
+    let new_dir = staging_dir.join("new");
+    let mut entries: Vec<StagedEntry> = Vec::new();
+    let remove = |entries: &mut Vec<StagedEntry>, path: &str| {
+        if target_path.join(path).exists() && !entries.iter().any(|e| e.path == path) {
+            entries.push(StagedEntry { path: path.to_string(), had_old: true, removed: true });
+        }
+    };
+    
+    if backup.incremental {
+        for path in &backup.added_files {
+            remove(&mut entries, path);
+        }
+        for path in &backup.saved_files {
+            // A file may have taken the place of one of the file's folders since
+            let ancestors: Vec<String> = std::path::Path::new(path).ancestors().skip(1)
+                .filter(|parent| !parent.as_os_str().is_empty() && target_path.join(parent).is_file())
+                .map(|parent| parent.to_string_lossy().to_string())
+                .collect();
+            for parent in ancestors {
+                remove(&mut entries, &parent);
+            }
+            copy_file(&files_dir.join(path), &new_dir.join(path))?;
+            entries.push(StagedEntry { path: path.clone(), had_old: target_path.join(path).exists(), removed: false });
+        }
+    } else {
+        // Older backups hold whole destination folders
+        for entry in &backup.entries {
+            copy_directory(&files_dir.join(entry), &new_dir.join(entry))?;
+            entries.push(StagedEntry { path: entry.clone(), had_old: target_path.join(entry).exists(), removed: false });
+        }
+    }
+    
+    // Folders the build created from scratch go away again
+    for entry in &backup.missing_entries {
+        remove(&mut entries, entry);
+    }
+    
+    if backup.had_package_json {
+        std::fs::copy(files_dir.join("package.json"), new_dir.join("package.json"))
+            .map_err(|e| format!("Failed to stage package.json: {}", e))?;
+        entries.push(StagedEntry {
+            path: "package.json".to_string(),
+            had_old: target_path.join("package.json").exists(),
+            removed: false,
+        });
+    } else {
+        remove(&mut entries, "package.json");
+    }
+    
+    // A folder moved aside as a whole takes everything inside it along
+    let moved_dirs: Vec<String> = entries.iter()
+        .filter(|e| e.had_old && target_path.join(&e.path).is_dir())
+        .map(|e| format!("{}/", e.path))
+        .collect();
+    entries.retain(|e| !moved_dirs.iter().any(|dir| e.path.starts_with(dir.as_str())));
+    
+    // Removals first, see stage_target_update
+    entries.sort_by_key(|entry| !entry.removed);
+    Ok(entries)
+}
+
+// Roll back every target touched by a build; stops at nothing, reports each target
+pub fn rollback_build(project_id: &str, build_id: &str) -> Result<Vec<Result<String, String>>, String> {
+    let backups = list_build_backups(project_id, build_id);
+    if backups.is_empty() {
+        return Err(format!("No backups found for build '{}'", build_id));
+    }
+    Ok(backups.iter().map(rollback_target).collect())
+}
+
+// Latest snapshot of a target, used for the quick rollback in the project view
+pub fn latest_target_backup(project_id: &str, target_id: &str) -> Option<TargetBackup> {
+    list_backups(project_id).into_iter().find(|b| b.target_id == target_id)
+}
+
+// Keep only the snapshots of the `retention` most recent builds
+pub fn prune_backups(project_id: &str, retention: usize) {
+    let mut build_ids: Vec<String> = Vec::new();
+    for backup in list_backups(project_id) {
+        if !build_ids.contains(&backup.build_id) {
+            build_ids.push(backup.build_id);
+        }
+    }
+    
+    for build_id in build_ids.iter().skip(retention) {
+        if let Err(e) = std::fs::remove_dir_all(get_build_backup_dir(project_id, build_id)) {
+            eprintln!("Failed to prune backup {}: {}", build_id, e);
+        }
+    }
+}
+
+pub fn format_duration_ms(duration_ms: u64) -> String {
+    let seconds = duration_ms / 1000;
+    let minutes = seconds / 60;
+    if minutes > 0 {
+        format!("{}m {}s", minutes, seconds % 60)
+    } else {
+        format!("{}.{}s", seconds, (duration_ms % 1000) / 100)
+    }
+}
+
+// How one build command ended, e.g. "failed with exit code 2 after 3.1s"
+pub fn describe_command_outcome(outcome: &CommandOutcome) -> String {
+    let duration = format_duration_ms(outcome.duration_ms);
+    match outcome.status {
+        CommandStatus::Succeeded => format!("succeeded in {}", duration),
+        CommandStatus::Failed => match outcome.exit_code {
+            Some(code) => format!("failed with exit code {} after {}", code, duration),
+            None => format!("failed after {}", duration),
+        },
+        CommandStatus::Cancelled => format!("cancelled after {}", duration),
+        CommandStatus::TimedOut => format!("timed out after {}", duration),
+        CommandStatus::NotRun => "not run".to_string(),
+    }
+}
+
+// Dependency graph
+
+// A project feeds another when one of its active targets lies inside the other's node_modules
+pub fn project_feeds(upstream: &Project, downstream: &Project) -> bool {
+    if upstream.id == downstream.id {
+        return false;
+    }
+    let node_modules = std::path::Path::new(&downstream.path).join("node_modules");
+    upstream.target_paths.iter()
+        .filter(|t| t.is_active)
+        .any(|t| std::path::Path::new(&t.path).starts_with(&node_modules))
+}
+
+// Projects that directly consume the given project
+pub fn downstream_projects<'a>(projects: &'a [Project], project: &Project) -> Vec<&'a Project> {
+    projects.iter().filter(|p| project_feeds(project, p)).collect()
+}
+
+// Projects the given project directly consumes
+pub fn upstream_projects<'a>(projects: &'a [Project], project: &Project) -> Vec<&'a Project> {
+    projects.iter().filter(|p| project_feeds(p, project)).collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum VisitState {
+    New,
+    InProgress,
+    Done,
+}
+
+// The project followed by everything downstream of it, each project after all of
+// the projects in the chain that feed it
+// Fails when the projects reachable from it form a cycle
+pub fn plan_cascade(projects: &[Project], project_id: &str) -> Result<Vec<Project>, String> {
+    fn visit(
+        projects: &[Project],
+        index: usize,
+        states: &mut [VisitState],
+        path: &mut Vec<usize>,
+        finished: &mut Vec<usize>,
+    ) -> Result<(), String> {
+        match states[index] {
+            VisitState::Done => return Ok(()),
+            VisitState::InProgress => {
+                let start = path.iter().position(|&i| i == index).unwrap_or(0);
+                let names: Vec<&str> = path[start..].iter()
+                    .chain(std::iter::once(&index))
+                    .map(|&i| projects[i].name.as_str())
+                    .collect();
+                return Err(format!("Dependency cycle: {}", names.join(" → ")));
+            }
+            VisitState::New => {}
+        }
+        
+        states[index] = VisitState::InProgress;
+        path.push(index);
+        for next in 0..projects.len() {
+            if project_feeds(&projects[index], &projects[next]) {
+                visit(projects, next, states, path, finished)?;
+            }
+        }
+        path.pop();
+        states[index] = VisitState::Done;
+        finished.push(index);
+        Ok(())
+    }
+    
+    let root = projects.iter().position(|p| p.id == project_id)
+        .ok_or_else(|| format!("Project '{}' not found", project_id))?;
+    let mut states = vec![VisitState::New; projects.len()];
+    let mut finished = Vec::new();
+    visit(projects, root, &mut states, &mut Vec::new(), &mut finished)?;
+    
+    // Reversed depth-first finish order is a topological order
+    Ok(finished.into_iter().rev().map(|i| projects[i].clone()).collect())
+}
+
+// Consumer discovery
+
+// How deep below a search root project folders are looked for
+const CONSUMER_SEARCH_DEPTH: usize = 6;
+
+// Folders to search for consumers: the configured ones, or the project's parent folder
+pub fn consumer_search_roots(project: &Project) -> Vec<String> {
+    let roots = load_settings().consumer_search_roots;
+    if !roots.is_empty() {
+        return roots;
+    }
+    std::path::Path::new(&project.path).parent()
+        .map(|parent| vec![parent.to_string_lossy().to_string()])
+        .unwrap_or_default()
+}
+
+// Every node_modules/<package name> below the roots whose package.json names the project's package
+// Each node_modules is searched for the package and for copies nested one dependency deep,
+// never further; installs that resolve back into the project itself (links) are skipped
+pub fn find_consumers(project: &Project, roots: &[String]) -> Result<Vec<ConsumerCandidate>, String> {
+    let package_name = get_package_name(&project.path)
+        .ok_or_else(|| format!("{}/package.json has no package name", project.path))?;
+    let source = std::fs::canonicalize(&project.path)
+        .map_err(|e| format!("Failed to resolve {}: {}", project.path, e))?;
+    
+    struct Search<'a> {
+        package_name: &'a str,
+        source: &'a std::path::Path,
+        found: Vec<std::path::PathBuf>,
+    }
+    
+    impl Search<'_> {
+        fn walk(&mut self, dir: &std::path::Path, depth: usize) {
+            let Ok(entries) = std::fs::read_dir(dir) else { return };
+            for entry in entries.filter_map(|e| e.ok()) {
+                // Symlinked folders are not followed, so links cannot loop
+                if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                    continue;
                 }
-            };
-            
-            // Clear the process handle after completion
-            process_handle.set(None);
-            
-            // Clean up script file
-            let _ = std::fs::remove_file(&script_path);
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name == "node_modules" {
+                    self.search_node_modules(&entry.path());
+                } else if !name.starts_with('.') && depth < CONSUMER_SEARCH_DEPTH {
+                    self.walk(&entry.path(), depth + 1);
+                }
+            }
+        }
+        
+        fn search_node_modules(&mut self, node_modules: &std::path::Path) {
+            self.check(node_modules.join(self.package_name));
             
-            match output {
-                Ok(output) => {
-                    if output.status.success() {
-                        let stdout = String::from_utf8_lossy(&output.stdout);
-                        results.push(format!("✅ All build commands completed successfully\n{}", stdout));
-                    } else {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        return Err(format!("❌ Build script failed: {}", stderr));
+            // Copies installed under another dependency, e.g. node_modules/b/node_modules/<package>
+            let Ok(entries) = std::fs::read_dir(node_modules) else { return };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('@') {
+                    let Ok(scoped) = std::fs::read_dir(entry.path()) else { continue };
+                    for package in scoped.filter_map(|e| e.ok()) {
+                        self.check(package.path().join("node_modules").join(self.package_name));
                     }
-                }
-                Err(e) => {
-                    return Err(format!("❌ Failed to execute build script: {}", e));
+                } else if !name.starts_with('.') {
+                    self.check(entry.path().join("node_modules").join(self.package_name));
                 }
             }
         }
-        Err(e) => {
-            return Err(format!("❌ Failed to create build script: {}", e));
+        
+        fn check(&mut self, path: std::path::PathBuf) {
+            let installed = path.to_string_lossy().to_string();
+            if get_package_name(&installed).as_deref() != Some(self.package_name) {
+                return;
+            }
+            match std::fs::canonicalize(&path) {
+                Ok(resolved) if !resolved.starts_with(self.source) && !self.found.contains(&path) => self.found.push(path),
+                _ => {}
+            }
         }
     }
     
-    results.push("\n📦 Build commands completed successfully!".to_string());
-    
-    // Step 2: Check if dist directory exists after build
-    progress_signal.set("Verifying build output...".to_string());
-    let dist_path = project_path.join("dist");
-    if !dist_path.exists() {
-        return Err("dist directory not found after build. Build commands may have failed.".to_string());
+    let mut search = Search { package_name: &package_name, source: &source, found: Vec::new() };
+    for root in roots {
+        let root = std::path::Path::new(root);
+        if !root.is_dir() {
+            return Err(format!("Search folder {} does not exist", root.display()));
+        }
+        search.walk(root, 0);
     }
     
-    results.push("\n📤 Updating target paths...".to_string());
-    
-    // Process each active target
-    for (index, target) in active_targets.iter().enumerate() {
-        progress_signal.set(format!("Updating target {} of {}: {}", index + 1, active_targets.len(), extract_project_name(&target.path)));
+    let mut candidates: Vec<ConsumerCandidate> = search.found.into_iter()
+        .map(|path| {
+            // <consumer>/node_modules/[@scope/]<package>
+            let consumer_path = path.ancestors()
+                .find(|a| a.file_name().map(|n| n == "node_modules").unwrap_or(false))
+                .and_then(|node_modules| node_modules.parent())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // Targets added from the CLI are stored with symlinks resolved
+            let resolved = std::fs::canonicalize(&path).ok();
+            let is_target = project.target_paths.iter().any(|t| {
+                std::path::Path::new(&t.path) == path || std::fs::canonicalize(&t.path).ok() == resolved
+            });
+            let path = path.to_string_lossy().to_string();
+            ConsumerCandidate {
+                installed_version: get_package_version(&path),
+                is_target,
+                consumer_path,
+                path,
+            }
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(candidates)
+}
+
+// Watch mode
+
+/// Watches a project's source tree and yields debounced batches of changed files
+pub struct ProjectWatcher {
+    watcher: notify::RecommendedWatcher, // Stops watching when dropped
+    receiver: tokio::sync::mpsc::UnboundedReceiver<std::path::PathBuf>,
+    root: std::path::PathBuf,
+    canonical_root: std::path::PathBuf, // Events carry canonical paths on some platforms (e.g. /private/var on macOS)
+    filter: WatchFilter,
+    watched: std::collections::HashSet<std::path::PathBuf>,
+    debounce: std::time::Duration,
+    pending: std::collections::BTreeSet<String>, // Kept if next_changes is dropped mid-batch
+}
+
+impl ProjectWatcher {
+    pub fn new(project: &Project) -> Result<Self, String> {
+        let root = std::path::PathBuf::from(&project.path);
+        let filter = WatchFilter::new(project)?;
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
         
-        let target_path = std::path::Path::new(&target.path);
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if event.kind.is_access() {
+                return;
+            }
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }).map_err(|e| format!("Failed to start file watcher: {}", e))?;
         
-        // Get current version from target's package.json
-        let current_version = get_package_version(&target.path)
-            .unwrap_or_else(|| "0.0.0".to_string());
+        let mut project_watcher = Self {
+            watcher,
+            receiver,
+            canonical_root: root.canonicalize().unwrap_or_else(|_| root.clone()),
+            root: root.clone(),
+            filter,
+            watched: std::collections::HashSet::new(),
+            debounce: std::time::Duration::from_millis(project.watch.debounce_ms),
+            pending: std::collections::BTreeSet::new(),
+        };
+        project_watcher.watch_tree(&root)
+            .map_err(|e| format!("Failed to watch {}: {}", project.path, e))?;
+        Ok(project_watcher)
+    }
+    
+    // Watch `dir` and every folder below it one by one, leaving out excluded folders and
+    // build output; a recursive watch would also cover node_modules and .git and run out
+    // of watches on large projects
+    fn watch_tree(&mut self, dir: &std::path::Path) -> Result<(), String> {
+        use notify::Watcher;
         
-        // Increment patch version
-        let new_version = increment_patch_version(&current_version);
+        let mut folders = vec![dir.to_path_buf()];
+        while let Some(folder) = folders.pop() {
+            if !self.watched.insert(folder.clone()) {
+                continue;
+            }
+            self.watcher.watch(&folder, notify::RecursiveMode::NonRecursive)
+                .map_err(|e| format!("{}: {}", folder.display(), e))?;
+            
+            let Ok(entries) = std::fs::read_dir(&folder) else { continue };
+            for entry in entries.filter_map(|e| e.ok()) {
+                // Symlinked folders are not followed
+                if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    continue;
+                }
+                let path = entry.path();
+                if self.relative_path(&path).is_some_and(|relative| !self.filter.skips_dir(&relative)) {
+                    folders.push(path);
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    // "src/index.ts" for a path inside the project, with forward slashes
+    fn relative_path(&self, path: &std::path::Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.canonical_root)
+            .or_else(|_| path.strip_prefix(&self.root))
+            .ok()?;
+        Some(relative.components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+    
+    fn handle_event_path(&mut self, path: std::path::PathBuf) {
+        let Some(relative) = self.relative_path(&path) else { return };
+        let path = self.root.join(&relative);
         
-        // Copy dist directory
-        let target_dist = target_path.join("dist");
-        if let Err(e) = copy_directory(&dist_path, &target_dist) {
-            results.push(format!("❌ Failed to copy dist to {}: {}", target.path, e));
-            continue;
+        if path.is_dir() {
+            // Folders created after the watch started need a watch of their own
+            if !relative.is_empty() && !self.watched.contains(&path) && !self.filter.skips_dir(&relative) {
+                if let Err(e) = self.watch_tree(&path) {
+                    eprintln!("Changes in {} will not be picked up: {}", relative, e);
+                }
+            }
+        } else if !path.exists() {
+            // A folder of the same name created later is watched afresh
+            self.watched.retain(|dir| !dir.starts_with(&path));
         }
         
-        // Copy package.json
-        let target_package_json = target_path.join("package.json");
-        if let Err(e) = std::fs::copy(&package_json_path, &target_package_json) {
-            results.push(format!("❌ Failed to copy package.json to {}: {}", target.path, e));
-            continue;
+        if self.filter.matches(&relative) {
+            self.pending.insert(relative);
+        }
+    }
+    
+    /// Wait for the next change, then keep collecting until the tree has been
+    /// quiet for the debounce period; returns the changed paths, sorted
+    pub async fn next_changes(&mut self) -> Option<Vec<String>> {
+        while self.pending.is_empty() {
+            let path = self.receiver.recv().await?;
+            self.handle_event_path(path);
         }
         
-        // Update version in target's package.json
-        if let Err(e) = update_package_version(&target.path, &new_version) {
-            results.push(format!("❌ Failed to update version in {}: {}", target.path, e));
-            continue;
+        while let Ok(Some(path)) = tokio::time::timeout(self.debounce, self.receiver.recv()).await {
+            self.handle_event_path(path);
         }
+        Some(std::mem::take(&mut self.pending).into_iter().collect())
+    }
+}
+
+/// Watches a single file and yields once per burst of changes to it
+pub struct FileWatcher {
+    _watcher: notify::RecommendedWatcher, // Stops watching when dropped
+    receiver: tokio::sync::mpsc::UnboundedReceiver<()>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &std::path::Path) -> Result<Self, String> {
+        use notify::Watcher;
         
-        results.push(format!("✅ Updated {} (v{} → v{})", target.path, current_version, new_version));
+        // The folder is watched rather than the file, atomic writes replace the file
+        let dir = path.parent()
+            .ok_or_else(|| format!("{:?} has no parent directory", path))?
+            .to_path_buf();
+        let name = path.file_name()
+            .ok_or_else(|| format!("{:?} has no file name", path))?
+            .to_os_string();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if !event.kind.is_access() && event.paths.iter().any(|p| p.file_name() == Some(name.as_os_str())) {
+                let _ = sender.send(());
+            }
+        }).map_err(|e| format!("Failed to start file watcher: {}", e))?;
+        
+        watcher.watch(&dir, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+        
+        Ok(Self { _watcher: watcher, receiver })
+    }
+    
+    /// Wait for the next change; events arriving close together count as one
+    pub async fn next_change(&mut self) -> Option<()> {
+        self.receiver.recv().await?;
+        while let Ok(Some(())) = tokio::time::timeout(std::time::Duration::from_millis(100), self.receiver.recv()).await {}
+        Some(())
+    }
+}
+
+/// Include/exclude globs of a project, plus the paths a build writes itself
+struct WatchFilter {
+    include: globset::GlobSet,
+    exclude: globset::GlobSet,
+    ignored_dirs: Vec<String>,
+}
+
+impl WatchFilter {
+    fn new(project: &Project) -> Result<Self, String> {
+        let include = build_glob_set(&project.watch.include)?;
+        let exclude = build_glob_set(&project.watch.exclude)?;
+        
+        // Rebuilding because the build wrote its own output would never stop
+        let ignored_dirs = resolve_output_dirs(project).into_iter()
+            .map(|dir| dir.source.trim_matches('/').to_string())
+            .filter(|dir| !dir.is_empty())
+            .collect();
+        
+        Ok(Self { include, exclude, ignored_dirs })
     }
     
-    progress_signal.set("Finalizing...".to_string());
+    fn matches(&self, relative: &str) -> bool {
+        if self.is_ignored(relative) {
+            return false;
+        }
+        self.include.is_match(relative) && !self.exclude.is_match(relative)
+    }
     
-    Ok(results.join("\n"))
+    fn is_ignored(&self, relative: &str) -> bool {
+        self.ignored_dirs.iter().any(|dir| relative == dir || relative.starts_with(&format!("{}/", dir)))
+    }
+    
+    // A folder nothing inside of could match, e.g. node_modules under "**/node_modules/**"
+    fn skips_dir(&self, relative: &str) -> bool {
+        self.is_ignored(relative) || self.exclude.is_match(relative) || self.exclude.is_match(format!("{}/", relative))
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<globset::GlobSet, String> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let glob = globset::Glob::new(pattern)
+            .map_err(|e| format!("Invalid glob {:?}: {}", pattern, e))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| format!("Invalid glob set: {}", e))
+}
+
+// Check the watch globs of a project without starting a watcher
+pub fn validate_watch_settings(settings: &WatchSettings) -> Result<(), String> {
+    build_glob_set(&settings.include)?;
+    build_glob_set(&settings.exclude)?;
+    Ok(())
 }
 
 // Cancel a running build process with tree kill
 pub async fn cancel_build_process(mut process_handle: dioxus::prelude::Signal<Option<tokio::process::Child>>) -> Result<(), String> {
-    if let Some(mut child) = process_handle.take() {
-        if let Some(pid) = child.id() {
-            println!("❌ Cancelling build process tree (PID: {})", pid);
-            match kill_process_tree(pid).await {
-                Ok(_) => {
-                    process_handle.set(None);
-                    Ok(())
-                }
-                Err(e) => {
-                    // Fallback to simple kill if tree kill fails
-                    println!("⚠️ Tree kill failed, using fallback: {}", e);
-                    match child.kill().await {
-                        Ok(_) => {
-                            process_handle.set(None);
-                            Ok(())
-                        }
-                        Err(e) => Err(format!("Failed to cancel process: {}", e))
-                    }
-                }
-            }
-        } else {
-            // No PID available, use simple kill
-            match child.kill().await {
-                Ok(_) => {
-                    process_handle.set(None);
-                    Ok(())
-                }
-                Err(e) => Err(format!("Failed to cancel process: {}", e))
+    match process_handle.take() {
+        Some(child) => {
+            kill_build_child(child).await?;
+            process_handle.set(None);
+            Ok(())
+        }
+        None => Err("No running process to cancel".to_string()),
+    }
+}
+
+// Cancel a build running with a TerminalReporter, e.g. from the watch loop
+pub async fn cancel_process_slot(slot: &ProcessSlot) -> Result<(), String> {
+    let child = slot.lock().map_err(|_| "Process slot is poisoned".to_string())?.take();
+    match child {
+        Some(child) => kill_build_child(child).await,
+        None => Err("No running process to cancel".to_string()),
+    }
+}
+
+async fn kill_build_child(mut child: tokio::process::Child) -> Result<(), String> {
+    if let Some(pid) = child.id() {
+        eprintln!("❌ Cancelling build process tree (PID: {})", pid);
+        match kill_process_tree(pid).await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // Fallback to simple kill if tree kill fails
+                eprintln!("⚠️ Tree kill failed, using fallback: {}", e);
+                child.kill().await
+                    .map_err(|e| format!("Failed to cancel process: {}", e))
             }
         }
     } else {
-        Err("No running process to cancel".to_string())
+        // No PID available, use simple kill
+        child.kill().await
+            .map_err(|e| format!("Failed to cancel process: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_args_round_trips_through_split_args() {
+        let cases: Vec<Vec<&str>> = vec![
+            vec!["npx", "tsc", "-p", "tsconfig.build.json"],
+            vec!["C:\\tools\\gen.exe", "--out", "dist\\lib"],
+            vec!["echo", "two words", "it's", "say \"hi\"", ""],
+            vec!["mixed", "it's a \\path\\", "back\\slash\"quote"],
+        ];
+        for case in cases {
+            let args: Vec<String> = case.iter().map(|arg| arg.to_string()).collect();
+            assert_eq!(split_args(&join_args(&args)).unwrap(), args);
+        }
     }
 }
@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 use dioxus::desktop::{WindowBuilder};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::process;
 
 mod types;
@@ -8,17 +8,35 @@ mod logic;
 mod pages;
 mod components;
 
-use pages::{Home, ProjectDetail, Settings};
+use pages::{Home, ProjectDetail, ProjectHistory, Settings, StoreRecovery};
 use logic::*;
 
 #[derive(Parser)]
 #[command(name = "library-build-management")]
 #[command(about = "A tool to manage library builds and deployments")]
 struct Cli {
+    /// Output format; with json, stdout carries only the JSON result
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+    /// Data folder to use instead of ~/.library-build-management (or LIBRARY_BUILD_MANAGEMENT_DIR)
+    #[arg(long, global = true)]
+    config_dir: Option<String>,
+    /// Configuration profile to use instead of the active one (or LIBRARY_BUILD_MANAGEMENT_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+// Set once from the parsed command line
+static OUTPUT_FORMAT: std::sync::OnceLock<OutputFormat> = std::sync::OnceLock::new();
+
 #[derive(Subcommand)]
 enum Commands {
     /// Build and update targets for a project
@@ -29,11 +47,425 @@ enum Commands {
         /// List all available projects
         #[arg(short, long)]
         list: bool,
+        /// Show what the build would do without running or writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Keep watching the project's sources and rebuild when they change
+        #[arg(short, long, conflicts_with = "dry_run")]
+        watch: bool,
+        /// Also build every managed project that consumes this one, in dependency order
+        #[arg(long, conflicts_with = "watch")]
+        cascade: bool,
     },
     /// List all projects
     List,
+    /// Show past builds of a project
+    History {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Maximum number of builds to list
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+        /// Only show builds with this status (succeeded, partially_failed, failed, cancelled, timed_out)
+        #[arg(long)]
+        status: Option<String>,
+        /// Only show builds whose commands, targets, versions or log contain this text
+        #[arg(long)]
+        search: Option<String>,
+        /// Show the full record (including the log) of one build, by ID or ID prefix
+        #[arg(long)]
+        show: Option<String>,
+    },
+    /// Restore targets to the state they had before a build
+    Rollback {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Build ID or ID prefix to roll back (defaults to the latest build with backups)
+        #[arg(short, long)]
+        build: Option<String>,
+        /// Only roll back this target (path or ID)
+        #[arg(short, long)]
+        target: Option<String>,
+        /// List available backups instead of restoring
+        #[arg(short, long)]
+        list: bool,
+    },
+    /// Add, remove, rename or inspect projects
+    Project {
+        #[command(subcommand)]
+        action: ProjectAction,
+    },
+    /// Choose which package.json scripts a build runs, and in which order
+    #[command(name = "commands")]
+    Scripts {
+        #[command(subcommand)]
+        action: CommandsAction,
+    },
+    /// Manage the folders a project's build is copied into
+    Target {
+        #[command(subcommand)]
+        action: TargetAction,
+    },
+    /// Set the environment variables and .env files a project's build commands get
+    Env {
+        #[command(subcommand)]
+        action: EnvAction,
+    },
+    /// Write project definitions to a portable JSON or TOML bundle
+    Export {
+        /// Bundle file; a .toml extension writes TOML, anything else JSON
+        file: String,
+        /// Project name or ID to export; repeat for several (default: all projects)
+        #[arg(short, long = "project")]
+        projects: Vec<String>,
+        /// Bundle format, overriding the file extension: json or toml
+        #[arg(long)]
+        format: Option<String>,
+        /// Write secret variable values as they are instead of masking them
+        #[arg(long)]
+        include_secrets: bool,
+    },
+    /// Add the projects from a bundle (or another projects.json)
+    Import {
+        /// Bundle file
+        file: String,
+        /// What to do with a project whose ID or name already exists: skip, overwrite or rename
+        #[arg(long, default_value = "skip")]
+        on_conflict: String,
+        /// Replace a path prefix, e.g. --remap /Users/alice=/home/bob; repeat for several
+        #[arg(long = "remap")]
+        remaps: Vec<String>,
+        /// Bundle format, overriding the file extension: json or toml
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Manage configuration profiles, each with its own projects
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum EnvAction {
+    /// Show the variables a build would get, secret values masked
+    List {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+    },
+    /// Set a variable, e.g. NODE_ENV=production
+    Set {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// KEY=VALUE
+        assignment: String,
+        /// Mask the value in the build log and history
+        #[arg(long)]
+        secret: bool,
+    },
+    /// Remove a variable
+    Unset {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Variable name
+        key: String,
+    },
+    /// Load a .env file before the project's own variables
+    AddFile {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// File path, relative to the project folder or absolute
+        path: String,
+        /// Mask every value of the file in the build log and history
+        #[arg(long)]
+        secret: bool,
+    },
+    /// Stop loading a .env file
+    RemoveFile {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// File path as added
+        path: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// List profiles; the active one is marked
+    List,
+    /// Create an empty profile
+    Create {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+    },
+    /// Make a profile the active one for the GUI and later CLI runs
+    Use {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProjectAction {
+    /// Add a project from a folder containing a package.json
+    Add {
+        /// Display name of the project
+        #[arg(short, long)]
+        name: String,
+        /// Project folder
+        #[arg(short, long)]
+        path: String,
+    },
+    /// Remove a project together with its build history and backups
+    Remove {
+        /// Project name or ID
+        project: String,
+    },
+    /// Give a project a new name
+    Rename {
+        /// Project name or ID
+        project: String,
+        /// New name
+        new_name: String,
+    },
+    /// Show the full configuration of a project
+    Show {
+        /// Project name or ID
+        project: String,
+    },
+    /// Import the packages of an npm, yarn or pnpm workspace as linked projects
+    ImportWorkspace {
+        /// Workspace root folder
+        #[arg(short, long)]
+        path: String,
+        /// Where the packages' scripts run: package (in each package folder) or workspace (from the root, filtered)
+        #[arg(long, default_value = "package")]
+        scope: String,
+        /// Only import these packages (by package name); repeat for several
+        #[arg(long = "package")]
+        packages: Vec<String>,
+        /// Only list the packages that would be imported
+        #[arg(short, long)]
+        list: bool,
+    },
+    /// Set where a workspace package's scripts run: package or workspace
+    Scope {
+        /// Project name or ID
+        project: String,
+        /// package or workspace
+        scope: String,
+    },
+    /// Change project settings; only the options given are changed
+    Set {
+        /// Project name or ID
+        project: String,
+        /// npm, pnpm, yarn or bun; auto detects it from the lockfile
+        #[arg(long)]
+        package_manager: Option<String>,
+        /// Build output folder as SOURCE or SOURCE:DESTINATION; repeat for several (replaces the current list)
+        #[arg(long = "output-dir")]
+        output_dirs: Vec<String>,
+        /// Go back to the output folders found in package.json
+        #[arg(long, conflicts_with = "output_dirs")]
+        infer_output_dirs: bool,
+        /// How target versions are bumped: patch, minor, major, prerelease, timestamp_prerelease, keep_source or unchanged
+        #[arg(long)]
+        version_bump: Option<String>,
+        /// Identifier for the prerelease strategies, e.g. local in 1.4.3-local.0
+        #[arg(long, requires = "version_bump")]
+        prerelease_id: Option<String>,
+        /// Glob of sources that trigger a rebuild in watch mode; repeat for several (replaces the current list)
+        #[arg(long = "watch-include")]
+        watch_include: Vec<String>,
+        /// Glob of sources watch mode ignores; repeat for several (replaces the current list, "" clears it)
+        #[arg(long = "watch-exclude")]
+        watch_exclude: Vec<String>,
+        /// Milliseconds of quiet before watch mode rebuilds
+        #[arg(long)]
+        watch_debounce: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommandsAction {
+    /// Add scripts to the end of the build
+    Select {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Script names from package.json
+        #[arg(required = true)]
+        commands: Vec<String>,
+    },
+    /// Remove scripts from the build
+    Deselect {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Script names
+        #[arg(required = true)]
+        commands: Vec<String>,
+    },
+    /// Run these steps first, in this order; the others follow unchanged
+    Reorder {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Step positions (from 1) or script names, in their new order
+        #[arg(required = true)]
+        commands: Vec<String>,
+    },
+    /// Stop the build when a step, or the whole build, runs longer than this
+    Timeout {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Step the limit applies to, by position or script name (default: the whole build)
+        #[arg(short, long)]
+        step: Option<String>,
+        /// Limit in seconds; 0 removes it
+        seconds: u64,
+    },
+    /// List the build steps in order
+    List {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+    },
+    /// Add a step to the end of the build: a script with arguments, a program, or a shell snippet
+    /// e.g. `--script build -- --mode lib`, `--program npx -- tsc -p tsconfig.build.json`, `--shell ./scripts/gen-icons.sh`
+    Add {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// package.json script to run through the package manager
+        #[arg(long, group = "kind", required = true)]
+        script: Option<String>,
+        /// Program to run directly, looked up in PATH or relative to the project
+        #[arg(long, group = "kind")]
+        program: Option<String>,
+        /// Snippet for the system shell (sh -c, or cmd /C on Windows)
+        #[arg(long, group = "kind")]
+        shell: Option<String>,
+        /// Stop the build when this step runs longer than this many seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Arguments for the script or program, after `--`
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Remove a step
+    Remove {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Step position (from 1) or script name
+        step: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TargetAction {
+    /// Add a target folder, e.g. a consumer's node_modules/<package>
+    Add {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target folder
+        path: String,
+        /// Add the target without enabling it
+        #[arg(long)]
+        inactive: bool,
+        /// Add and update the target even if its package name does not match
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove a target
+    Remove {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+    },
+    /// Include a target in builds
+    Enable {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+    },
+    /// Skip a target in builds
+    Disable {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+    },
+    /// Update a target even if its package name does not match
+    Force {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+    },
+    /// Only update a target while its package name matches again
+    Unforce {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+    },
+    /// Set how a target's version is bumped, overriding the project's strategy
+    Set {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Target path or ID
+        target: String,
+        /// patch, minor, major, prerelease, timestamp_prerelease, keep_source, unchanged, or default to follow the project
+        #[arg(long)]
+        version_bump: String,
+        /// Identifier for the prerelease strategies, e.g. local in 1.4.3-local.0
+        #[arg(long)]
+        prerelease_id: Option<String>,
+    },
+    /// Find installed copies of the project's package in other projects' node_modules
+    Find {
+        /// Project name or ID
+        #[arg(short, long)]
+        project: String,
+        /// Folder to search; repeat for several (default: the folders set in Settings)
+        #[arg(long = "root")]
+        roots: Vec<String>,
+        /// Add the copies found as targets; with more than one, pick them with --path
+        #[arg(long)]
+        add: bool,
+        /// Copy to add, by its path or the project it is installed in; repeat for several
+        #[arg(long = "path", requires = "add")]
+        paths: Vec<String>,
+    },
 }
 
+/// Bumped whenever projects.json changes on disk; pages reload their projects when it does
+#[derive(Clone, Copy)]
+pub struct StoreRevision(pub Signal<u64>);
+
+/// The projects.json in use; set it after switching profile or data folder
+#[derive(Clone, Copy)]
+pub struct StoreFile(pub Signal<std::path::PathBuf>);
+
 #[derive(Debug, Clone, Routable, PartialEq)]
 #[rustfmt::skip]
 enum Route {
@@ -41,6 +473,8 @@ enum Route {
     Home {},
     #[route("/project/:id")]
     ProjectDetail { id: String },
+    #[route("/project/:id/history")]
+    ProjectHistory { id: String },
     #[route("/settings")]
     Settings {},
 }
@@ -72,10 +506,40 @@ fn main() {
     // If arguments are provided, try to parse them as CLI commands
     match Cli::try_parse() {
         Ok(cli) => {
+            let _ = OUTPUT_FORMAT.set(cli.output);
+            if let Some(dir) = &cli.config_dir {
+                set_data_dir_override(std::path::PathBuf::from(absolute_path(dir)));
+            }
+            if let Some(profile) = cli.profile {
+                if let Err(e) = ensure_profile_exists(&profile) {
+                    exit_cli_error(&e);
+                }
+                set_profile_override(profile);
+            } else if let Err(e) = check_env_profile() {
+                exit_cli_error(&e);
+            }
+            // Every command works on the project store; don't let one run against a file that cannot be read
+            if let Err(e) = load_project_store() {
+                exit_cli_error(&format!(
+                    "{}\nFix {} by hand, or open the GUI to restore a backup from {}",
+                    e,
+                    get_projects_file().display(),
+                    get_data_dir().display()
+                ));
+            }
+            recover_all_interrupted_updates();
             match cli.command {
-                Some(Commands::Build { project, list }) => {
+                Some(Commands::Build { project, list, dry_run, watch, cascade }) => {
                     if list {
                         list_projects_cli();
+                    } else if cascade {
+                        let rt = tokio::runtime::Runtime::new().unwrap();
+                        rt.block_on(cascade_cli(&project, dry_run));
+                    } else if dry_run {
+                        dry_run_cli(&project);
+                    } else if watch {
+                        let rt = tokio::runtime::Runtime::new().unwrap();
+                        rt.block_on(watch_project_cli(&project));
                     } else {
                         let rt = tokio::runtime::Runtime::new().unwrap();
                         rt.block_on(build_project_cli(&project));
@@ -84,6 +548,33 @@ fn main() {
                 Some(Commands::List) => {
                     list_projects_cli();
                 }
+                Some(Commands::History { project, limit, status, search, show }) => {
+                    history_cli(&project, limit, status.as_deref(), search.as_deref(), show.as_deref());
+                }
+                Some(Commands::Rollback { project, build, target, list }) => {
+                    rollback_cli(&project, build.as_deref(), target.as_deref(), list);
+                }
+                Some(Commands::Project { action }) => {
+                    project_cli(action);
+                }
+                Some(Commands::Scripts { action }) => {
+                    commands_cli(action);
+                }
+                Some(Commands::Target { action }) => {
+                    target_cli(action);
+                }
+                Some(Commands::Env { action }) => {
+                    env_cli(action);
+                }
+                Some(Commands::Profile { action }) => {
+                    profile_cli(action);
+                }
+                Some(Commands::Export { file, projects, format, include_secrets }) => {
+                    export_cli(&file, &projects, format.as_deref(), include_secrets);
+                }
+                Some(Commands::Import { file, on_conflict, remaps, format }) => {
+                    import_cli(&file, &on_conflict, &remaps, format.as_deref());
+                }
                 None => {
                     // This shouldn't happen with proper clap setup, but launch GUI as fallback
                     launch_gui();
@@ -105,6 +596,10 @@ fn main() {
 }
 
 fn launch_gui() {
+    if let Err(e) = check_env_profile() {
+        eprintln!("{}; using profile '{}' instead", e, active_profile());
+    }
+    recover_all_interrupted_updates();
     dioxus::LaunchBuilder::desktop()
         .with_cfg(make_config())
         .launch(App);
@@ -112,6 +607,31 @@ fn launch_gui() {
 
 #[component]
 fn App() -> Element {
+    // An unreadable project store has to be dealt with before anything can overwrite it
+    let mut store_error = use_signal(|| load_project_store().err());
+    let mut store_revision = use_context_provider(|| StoreRevision(Signal::new(0)));
+    let store_file = use_context_provider(|| StoreFile(Signal::new(get_projects_file())));
+
+    // Pick up changes other processes (e.g. the CLI) make to the project store;
+    // starts over on the new file when the profile or data folder is switched
+    let _store_watch = use_resource(move || async move {
+        let path = store_file.0();
+        store_error.set(load_project_store().err());
+        *store_revision.0.write() += 1;
+
+        let mut watcher = match FileWatcher::new(&path) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Projects file changes will not be picked up: {}", e);
+                return;
+            }
+        };
+        while watcher.next_change().await.is_some() {
+            store_error.set(load_project_store().err());
+            *store_revision.0.write() += 1;
+        }
+    });
+
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
@@ -119,7 +639,14 @@ fn App() -> Element {
         document::Stylesheet {
             href: asset!("/assets/tailwind.css")
         }
-        Router::<Route> {}
+        if let Some(error) = store_error() {
+            StoreRecovery {
+                error,
+                on_recovered: move |_| store_error.set(load_project_store().err()),
+            }
+        } else {
+            Router::<Route> {}
+        }
     }
 }
 
@@ -136,11 +663,35 @@ fn make_window() -> WindowBuilder {
 }
 
 // CLI Functions
+
+// Find project by name (case-insensitive) or ID
+fn find_project<'a>(projects: &'a [types::Project], identifier: &str) -> Option<&'a types::Project> {
+    projects.iter().find(|p| {
+        p.name.to_lowercase() == identifier.to_lowercase() ||
+        p.id == identifier
+    })
+}
+
+fn exit_project_not_found(project_identifier: &str) -> ! {
+    if json_output() {
+        exit_cli_error(&format!("Project '{}' not found", project_identifier));
+    }
+    println!("❌ Error: Project '{}' not found.", project_identifier);
+    println!();
+    println!("💡 Available projects:");
+    list_projects_cli();
+    process::exit(1);
+}
 fn list_projects_cli() {
     let projects = load_projects();
     
+    if json_output() {
+        print_json(&projects.iter().map(mask_secret_env).collect::<Vec<_>>());
+        return;
+    }
+    
     if projects.is_empty() {
-        println!("No projects found. Use the GUI to add projects first.");
+        println!("No projects found. Add one with `project add --name <name> --path <folder>` or from the GUI.");
         return;
     }
     
@@ -150,7 +701,9 @@ fn list_projects_cli() {
     for project in projects {
         println!("📦 {} ({})", project.name, project.id);
         println!("   Path: {}", project.path);
-        println!("   Build commands: {:?}", project.selected_build_commands);
+        println!("   Package manager: {}", resolve_package_manager(&project));
+        println!("   Build commands: {}", format_selected_commands(&project));
+        println!("   Build output: {}", format_output_dirs(&resolve_output_dirs(&project)));
         println!("   Active targets: {}", 
             project.target_paths.iter().filter(|p| p.is_active).count()
         );
@@ -161,39 +714,30 @@ fn list_projects_cli() {
 async fn build_project_cli(project_identifier: &str) {
     let projects = load_projects();
     
-    // Find project by name or ID
-    let project = projects.iter().find(|p| {
-        p.name.to_lowercase() == project_identifier.to_lowercase() || 
-        p.id == project_identifier
-    });
+    let project = find_project(&projects, project_identifier);
     
     match project {
+        Some(project) if json_output() => {
+            print_plan_warnings(plan_build(project));
+            // Build output goes to stderr, the result to stdout
+            let (result, record) = build_and_update_project_with(project, &mut TerminalReporter::stderr_only()).await;
+            print_json(&build_result_json(&result, record.as_ref()));
+            if result.is_err() {
+                process::exit(1);
+            }
+        }
         Some(project) => {
             println!("🔨 Building project: {}", project.name);
             println!("📁 Path: {}", project.path);
             
-            if project.selected_build_commands.is_empty() {
-                println!("❌ Error: No build commands selected for this project.");
-                println!("   Use the GUI to configure build commands first.");
-                process::exit(1);
-            }
-            
-            let active_targets = project.target_paths.iter().filter(|p| p.is_active).count();
-            if active_targets == 0 {
-                println!("❌ Error: No active target paths for this project.");
-                println!("   Use the GUI to configure target paths first.");
-                process::exit(1);
-            }
-            
-            println!("🚀 Executing {} build commands...", project.selected_build_commands.len());
-            for (i, cmd) in project.selected_build_commands.iter().enumerate() {
-                println!("   {}. {}", i + 1, cmd);
-            }
-            
-            println!("📤 Will update {} active targets", active_targets);
+            let plan = match plan_build(project) {
+                Ok(plan) => plan,
+                Err(e) => exit_plan_error(&e),
+            };
+            print_build_plan(&plan, false);
             println!();
             
-            // Execute the build and update
+            // Execute the build and update, streaming output as it runs
             match build_and_update_project(project).await {
                 Ok(output) => {
                     println!("✅ Build and update completed successfully!");
@@ -207,13 +751,1382 @@ async fn build_project_cli(project_identifier: &str) {
                 }
             }
         }
-        None => {
-            println!("❌ Error: Project '{}' not found.", project_identifier);
+        None => exit_project_not_found(project_identifier),
+    }
+}
+
+// Build once, then rebuild whenever the watched sources change
+// A change arriving mid-build cancels the running build and starts a new one
+async fn watch_project_cli(project_identifier: &str) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project.clone(),
+        None => exit_project_not_found(project_identifier),
+    };
+    
+    let mut watcher = match ProjectWatcher::new(&project) {
+        Ok(watcher) => watcher,
+        Err(e) => exit_cli_error(&e),
+    };
+    
+    status_line(&format!("👀 Watching project: {}", project.name));
+    status_line(&format!("   Include: {}", project.watch.include.join(", ")));
+    status_line(&format!("   Exclude: {}", project.watch.exclude.join(", ")));
+    status_line("   Press Ctrl+C to stop");
+    
+    // Everything the watcher was built from; see WatchFilter
+    let watch_config = |project: &types::Project| (project.path.clone(), project.watch.clone(), resolve_output_dirs(project));
+    let mut watched = watch_config(&project);
+    
+    // The first build runs straight away
+    let mut pending = Some(Vec::new());
+    
+    loop {
+        let changes = match pending.take() {
+            Some(changes) => changes,
+            None => match watcher.next_changes().await {
+                Some(changes) => changes,
+                None => break,
+            },
+        };
+        if !changes.is_empty() {
+            status_line(&format!("\n🔄 {} file(s) changed: {}", changes.len(), changes.join(", ")));
+        }
+        
+        // Pick up configuration edits made while watching
+        let project = load_projects().into_iter()
+            .find(|p| p.id == project.id)
+            .unwrap_or_else(|| project.clone());
+        
+        if watch_config(&project) != watched {
+            match ProjectWatcher::new(&project) {
+                Ok(new_watcher) => {
+                    watcher = new_watcher;
+                    watched = watch_config(&project);
+                    status_line("👀 Watch settings changed, now watching:");
+                    status_line(&format!("   Include: {}", project.watch.include.join(", ")));
+                    status_line(&format!("   Exclude: {}", project.watch.exclude.join(", ")));
+                }
+                Err(e) => status_line(&format!("⚠️  Keeping the previous watch settings: {}", e)),
+            }
+        }
+        
+        print_plan_warnings(plan_build(&project));
+        let mut reporter = if json_output() { TerminalReporter::stderr_only() } else { TerminalReporter::default() };
+        let process_slot = reporter.process_slot();
+        let build = build_and_update_project_with(&project, &mut reporter);
+        tokio::pin!(build);
+        
+        let (result, record) = loop {
+            tokio::select! {
+                result = &mut build => break result,
+                Some(changes) = watcher.next_changes(), if pending.is_none() => {
+                    status_line("🔄 Sources changed during the build, restarting...");
+                    // Fails harmlessly when the build is already past its commands
+                    let _ = cancel_process_slot(&process_slot).await;
+                    pending = Some(changes);
+                }
+            }
+        };
+        
+        // One JSON document per line, one line per build
+        if json_output() {
+            println!("{}", serde_json::to_string(&build_result_json(&result, record.as_ref())).unwrap_or_default());
+            continue;
+        }
+        
+        let time = chrono::Local::now().format("%H:%M:%S");
+        match result {
+            Ok(output) => {
+                println!("{}", output);
+                println!("✅ [{}] Build and update completed, watching for changes...", time);
+            }
+            Err(error) => println!("❌ [{}] Build failed: {}\n   Watching for changes...", time, error),
+        }
+    }
+}
+
+// Build a project and then everything downstream of it, stopping at the first failure
+// A partially failed build counts as a failure: its consumers would get stale code
+async fn cascade_cli(project_identifier: &str, dry_run: bool) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project,
+        None => exit_project_not_found(project_identifier),
+    };
+    let order = match plan_cascade(&projects, &project.id) {
+        Ok(order) => order,
+        Err(e) => exit_cli_error(&e),
+    };
+    
+    let chain = order.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(" → ");
+    status_line(&format!("🔗 Cascade: {}", chain));
+    
+    if dry_run {
+        let mut previews = Vec::new();
+        for (index, project) in order.iter().enumerate() {
+            if let Some(reason) = cascade_skip_reason(project) {
+                if !json_output() {
+                    println!();
+                    println!("⏭️ [{}/{}] {}: {}", index + 1, order.len(), project.name, reason);
+                }
+                previews.push(serde_json::json!({
+                    "project_id": project.id,
+                    "project_name": project.name,
+                    "plan": null,
+                    "skipped": reason,
+                }));
+                continue;
+            }
+            let preview = preview_cascade_step(project);
+            if !json_output() {
+                println!();
+                println!("🔍 [{}/{}] {}", index + 1, order.len(), project.name);
+                match &preview {
+                    Ok(plan) => print_build_plan(plan, true),
+                    Err(e) => println!("❌ {}", e),
+                }
+            }
+            previews.push(serde_json::json!({
+                "project_id": project.id,
+                "project_name": project.name,
+                "plan": preview.as_ref().ok(),
+                "error": preview.as_ref().err(),
+            }));
+        }
+        if json_output() {
+            print_json(&previews);
+        } else {
+            println!();
+            println!("Nothing was run or written.");
+        }
+        return;
+    }
+    
+    let mut builds = Vec::new();
+    let mut failed_at = None;
+    for (index, project) in order.iter().enumerate() {
+        status_line(&format!("\n🔨 [{}/{}] Building {}", index + 1, order.len(), project.name));
+        if cascade_skip_reason(project).is_none() {
+            print_plan_warnings(plan_cascade_step(project));
+        }
+        
+        let mut reporter = if json_output() { TerminalReporter::stderr_only() } else { TerminalReporter::default() };
+        let (result, record) = build_cascade_step(project, &mut reporter).await;
+        // No record with a successful result means the project was passed over
+        let succeeded = result.is_ok()
+            && record.as_ref().is_none_or(|r| r.status == types::BuildStatus::Succeeded);
+        
+        match &result {
+            Ok(output) => status_line(output),
+            Err(e) => status_line(&format!("❌ Build failed: {}", e)),
+        }
+        builds.push(build_result_json(&result, record.as_ref()));
+        
+        if !succeeded {
+            failed_at = Some(index);
+            break;
+        }
+    }
+    
+    let skipped: Vec<&str> = match failed_at {
+        Some(index) => order[index + 1..].iter().map(|p| p.name.as_str()).collect(),
+        None => Vec::new(),
+    };
+    
+    if json_output() {
+        print_json(&serde_json::json!({
+            "success": failed_at.is_none(),
+            "order": order.iter().map(|p| &p.id).collect::<Vec<_>>(),
+            "builds": builds,
+            "skipped": skipped,
+        }));
+    } else {
+        println!();
+        match failed_at {
+            None => println!("✅ Cascade completed: {}", chain),
+            Some(index) => {
+                println!("⛔ Cascade stopped: {} did not build cleanly", order[index].name);
+                if !skipped.is_empty() {
+                    println!("   Not built: {}", skipped.join(", "));
+                }
+            }
+        }
+    }
+    
+    if failed_at.is_some() {
+        process::exit(1);
+    }
+}
+
+fn dry_run_cli(project_identifier: &str) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project,
+        None => exit_project_not_found(project_identifier),
+    };
+    
+    if json_output() {
+        match preview_build(project) {
+            Ok(plan) => print_json(&plan),
+            Err(e) => exit_plan_error(&e),
+        }
+        return;
+    }
+    
+    println!("🔍 Dry run for project: {}", project.name);
+    println!("📁 Path: {}", project.path);
+    match preview_build(project) {
+        Ok(plan) => {
+            print_build_plan(&plan, true);
             println!();
-            println!("💡 Available projects:");
-            list_projects_cli();
+            println!("Nothing was run or written.");
+        }
+        Err(e) => exit_plan_error(&e),
+    }
+}
+
+fn exit_plan_error(error: &str) -> ! {
+    if json_output() {
+        exit_cli_error(error);
+    }
+    println!("❌ Error: {}.", error);
+    println!("   Configure the project with the `commands` and `target` subcommands, or from the GUI.");
+    process::exit(1);
+}
+
+fn print_build_plan(plan: &types::BuildPlan, show_files: bool) {
+    println!("📦 Package manager: {}", plan.package_manager);
+    println!("🚀 Executing {} build commands...", plan.command_lines.len());
+    for (i, line) in plan.command_lines.iter().enumerate() {
+        match plan.command_timeouts.get(i).copied().flatten() {
+            Some(limit) => println!("   {}. {} (timeout {}s)", i + 1, line, limit),
+            None => println!("   {}. {}", i + 1, line),
+        }
+    }
+    if let Some(limit) = plan.build_timeout_secs {
+        println!("⏱️  Build timeout: {}s", limit);
+    }
+    
+    println!("📂 Build output: {}", format_output_dirs(&plan.output_dirs));
+    if !plan.env.is_empty() {
+        println!("🌱 Environment: {}", plan.env.join(", "));
+    }
+    println!("📤 Will update {} active targets:", plan.targets.len());
+    for target in &plan.targets {
+        match (&target.current_version, &target.new_version, &target.error) {
+            (_, _, Some(e)) => println!("   • {} (⚠️ {})", target.path, e),
+            (Some(current), Some(new), None) => println!("   • {} (v{} → v{}, {})", target.path, current, new, target.version_bump),
+            _ => println!("   • {}", target.path),
+        }
+        
+        if show_files {
+            let count = |kind| target.file_changes.iter().filter(|c| c.kind == kind).count();
+            println!("     {} added, {} changed, {} removed",
+                count(types::FileChangeKind::Added),
+                count(types::FileChangeKind::Changed),
+                count(types::FileChangeKind::Removed),
+            );
+            for change in &target.file_changes {
+                let marker = match change.kind {
+                    types::FileChangeKind::Added => "+",
+                    types::FileChangeKind::Changed => "~",
+                    types::FileChangeKind::Removed => "-",
+                };
+                println!("     {} {}", marker, change.path);
+            }
+        }
+    }
+    
+    for warning in &plan.warnings {
+        println!("⚠️  {}", warning);
+    }
+}
+
+fn history_cli(project_identifier: &str, limit: usize, status: Option<&str>, search: Option<&str>, show: Option<&str>) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project,
+        None => exit_project_not_found(project_identifier),
+    };
+    
+    if let Some(record_id) = show {
+        match find_build_record(&project.id, record_id) {
+            Some(record) if json_output() => print_json(&record),
+            Some(record) => print_build_record(&record),
+            None => exit_cli_error(&format!("No single build matches '{}'", record_id)),
+        }
+        return;
+    }
+    
+    let status_filter = match status {
+        Some(name) => match types::BuildStatus::from_name(name) {
+            Some(status) => Some(status),
+            None => {
+                let names: Vec<&str> = types::BuildStatus::ALL.iter().map(|s| s.as_str()).collect();
+                exit_cli_error(&format!("Unknown status '{}'. Use {}", name, names.join(", ")))
+            }
+        },
+        None => None,
+    };
+    
+    let records = filter_build_history(&load_build_history(&project.id), status_filter, search.unwrap_or(""));
+    if json_output() {
+        // Logs are left out of the listing; `--show` includes them
+        let records: Vec<types::BuildRecord> = records.into_iter()
+            .take(limit)
+            .map(|record| types::BuildRecord { log: Vec::new(), ..record })
+            .collect();
+        print_json(&records);
+        return;
+    }
+    if records.is_empty() {
+        println!("No builds recorded for '{}'.", project.name);
+        return;
+    }
+    
+    println!("Build history for {}:", project.name);
+    println!("{:-<60}", "");
+    for record in records.iter().take(limit) {
+        println!("{} {}  {}  {:<16} ⏱️ {}",
+            record.status.icon(),
+            &record.id[..8],
+            record.started_at.format("%Y-%m-%d %H:%M:%S"),
+            record.status.to_string(),
+            format_duration_ms(record.duration_ms),
+        );
+        println!("   Commands: {} {}", record.package_manager, record.commands.join(" → "));
+        for target in &record.targets {
+            print_target_outcome(target);
+        }
+    }
+    if records.len() > limit {
+        println!();
+        println!("… {} older builds not shown (use --limit)", records.len() - limit);
+    }
+}
+
+fn rollback_cli(project_identifier: &str, build_id: Option<&str>, target_identifier: Option<&str>, list: bool) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project,
+        None => exit_project_not_found(project_identifier),
+    };
+    
+    let all_backups = list_backups(&project.id);
+    if list && json_output() {
+        print_json(&all_backups);
+        return;
+    }
+    if all_backups.is_empty() {
+        if list {
+            println!("No backups available for '{}'.", project.name);
+            return;
+        }
+        // Nothing to roll back is a failure, reported as JSON with --output json
+        exit_cli_error(&format!("No backups available for '{}'", project.name));
+    }
+    
+    if list {
+        println!("Backups for {}:", project.name);
+        println!("{:-<60}", "");
+        for backup in &all_backups {
+            println!("🗄️  build {}  {}  {} (v{})",
+                &backup.build_id[..8],
+                backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+                backup.target_path,
+                backup.version.as_deref().unwrap_or("?"),
+            );
+        }
+        return;
+    }
+    
+    // Latest build with backups unless one was given
+    let build_id = build_id.unwrap_or(&all_backups[0].build_id);
+    let mut backups = list_build_backups(&project.id, build_id);
+    if backups.is_empty() {
+        exit_cli_error(&format!("No single build with backups matches '{}'", build_id));
+    }
+    
+    if let Some(target_identifier) = target_identifier {
+        backups.retain(|b| b.target_id == target_identifier || b.target_path == target_identifier);
+        if backups.is_empty() {
+            exit_cli_error(&format!("Build '{}' has no backup for target '{}'", build_id, target_identifier));
+        }
+    }
+    
+    status_line(&format!("↺ Rolling back {} target(s) of build {}", backups.len(), &backups[0].build_id[..8]));
+    let mut failed = false;
+    let mut results = Vec::new();
+    for backup in &backups {
+        let result = rollback_target(backup);
+        match &result {
+            Ok(message) => status_line(message),
+            Err(e) => {
+                status_line(&format!("❌ {}: {}", backup.target_path, e));
+                failed = true;
+            }
+        }
+        results.push(serde_json::json!({
+            "build_id": backup.build_id,
+            "target_id": backup.target_id,
+            "target_path": backup.target_path,
+            "restored_version": backup.version,
+            "success": result.is_ok(),
+            "error": result.err(),
+        }));
+    }
+    
+    if json_output() {
+        print_json(&results);
+    }
+    
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn print_target_outcome(target: &types::TargetOutcome) {
+    match (&target.old_version, &target.new_version, &target.error) {
+        (_, _, Some(error)) => println!("   ❌ {}: {}", target.path, error),
+        (Some(old), Some(new), None) => println!("   ✅ {} (v{} → v{}, {})", target.path, old, new, target.files),
+        _ => println!("   ✅ {}", target.path),
+    }
+}
+
+fn print_build_record(record: &types::BuildRecord) {
+    println!("{} Build {} ({})", record.status.icon(), record.id, record.status);
+    println!("   Project:  {}", record.project_name);
+    println!("   Started:  {}", record.started_at.format("%Y-%m-%d %H:%M:%S"));
+    println!("   Finished: {}", record.finished_at.format("%Y-%m-%d %H:%M:%S"));
+    println!("   Duration: {}", format_duration_ms(record.duration_ms));
+    println!("   Commands: {} {}", record.package_manager, record.commands.join(" → "));
+    for (index, outcome) in record.command_outcomes.iter().enumerate() {
+        println!("     {}. {} {}", index + 1, outcome.command, describe_command_outcome(outcome));
+    }
+    if let Some(code) = record.exit_code {
+        println!("   Exit code: {}", code);
+    }
+    if let Some(hit) = &record.timeout {
+        println!("   Timeout: {}", hit);
+    }
+    if let Some(error) = &record.error {
+        println!("   Error: {}", error);
+    }
+    for target in &record.targets {
+        print_target_outcome(target);
+    }
+    println!();
+    println!("📋 Log:");
+    for line in &record.log {
+        println!("[{}] {}", line.timestamp.format("%H:%M:%S"), line.text);
+    }
+}
+
+fn project_cli(action: ProjectAction) {
+    match action {
+        ProjectAction::Add { name, path } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                exit_cli_error("Project name cannot be empty");
+            }
+            if find_project(&load_projects(), &name).is_some() {
+                exit_cli_error(&format!("A project named '{}' already exists", name));
+            }
+            
+            let path = absolute_path(&path);
+            if !std::path::Path::new(&path).join("package.json").exists() {
+                exit_cli_error(&format!("No package.json found in {}", path));
+            }
+            
+            let project = create_project(name, path);
+            let added = update_projects(|projects| {
+                // Checked again under the store lock, another process may have added it meanwhile
+                if find_project(projects, &project.name).is_some() {
+                    return Err(format!("A project named '{}' already exists", project.name));
+                }
+                projects.push(project.clone());
+                Ok(())
+            });
+            if let Err(e) = added {
+                exit_cli_error(&e);
+            }
+            
+            if json_output() {
+                print_json(&project);
+                return;
+            }
+            println!("✅ Added project {} ({})", project.name, project.id);
+            println!("   Available scripts: {}", project.build_commands.join(", "));
+            println!("   Select the ones to build with `commands select --project \"{}\" <script>...`", project.name);
+        }
+        ProjectAction::Remove { project } => {
+            let projects = load_projects();
+            let (id, name) = match find_project(&projects, &project) {
+                Some(project) => (project.id.clone(), project.name.clone()),
+                None => exit_project_not_found(&project),
+            };
+            match delete_project(&name) {
+                Ok(()) if json_output() => print_json(&serde_json::json!({ "removed": { "id": id, "name": name } })),
+                Ok(()) => println!("🗑️  Removed project {}", name),
+                Err(e) => exit_cli_error(&e),
+            }
+        }
+        ProjectAction::Rename { project, new_name } => {
+            let new_name = new_name.trim().to_string();
+            if new_name.is_empty() {
+                exit_cli_error("Project name cannot be empty");
+            }
+            let projects = load_projects();
+            if let Some(existing) = find_project(&projects, &new_name) {
+                if find_project(&projects, &project).map(|p| &p.id) != Some(&existing.id) {
+                    exit_cli_error(&format!("A project named '{}' already exists", new_name));
+                }
+            }
+            update_project_cli(&project, |p| {
+                let old_name = std::mem::replace(&mut p.name, new_name.clone());
+                Ok(format!("✅ Renamed {} to {}", old_name, p.name))
+            });
+        }
+        ProjectAction::ImportWorkspace { path, scope, packages, list } => {
+            let scope = parse_build_scope(&scope);
+            let root = absolute_path(&path);
+            let mut found = match find_workspace_packages(&root) {
+                Ok(found) => found,
+                Err(e) => exit_cli_error(&e),
+            };
+            if !packages.is_empty() {
+                if let Some(missing) = packages.iter().find(|name| !found.iter().any(|p| &&p.name == name)) {
+                    exit_cli_error(&format!("No workspace package named '{}'", missing));
+                }
+                found.retain(|p| packages.contains(&p.name));
+            }
+            
+            if list {
+                if json_output() {
+                    print_json(&found);
+                } else {
+                    println!("Workspace packages in {}:", root);
+                    for package in &found {
+                        println!("📦 {} (v{})  {}", package.name, package.version.as_deref().unwrap_or("?"), package.path);
+                    }
+                }
+                return;
+            }
+            
+            let imported = update_projects(|projects| {
+                let mut imported = Vec::new();
+                for package in &found {
+                    // Already managed, by folder or by name
+                    if projects.iter().any(|p| p.path == package.path || p.name.to_lowercase() == package.name.to_lowercase()) {
+                        status_line(&format!("⏭️  {} is already a project, skipped", package.name));
+                        continue;
+                    }
+                    let project = create_workspace_project(&root, package, scope);
+                    status_line(&format!("✅ Imported {} ({})", project.name, project.id));
+                    projects.push(project.clone());
+                    imported.push(project);
+                }
+                Ok(imported)
+            });
+            let imported = match imported {
+                Ok(imported) => imported,
+                Err(e) => exit_cli_error(&e),
+            };
+            
+            if json_output() {
+                print_json(&imported);
+            } else {
+                println!("Imported {} of {} workspace packages.", imported.len(), found.len());
+            }
+        }
+        ProjectAction::Scope { project, scope } => {
+            let scope = parse_build_scope(&scope);
+            update_project_cli(&project, |p| match p.workspace.as_mut() {
+                Some(link) => {
+                    link.scope = scope;
+                    Ok(format!("✅ {} scripts now run at {} scope", p.name, scope))
+                }
+                None => Err(format!("'{}' was not imported from a workspace", p.name)),
+            });
+        }
+        ProjectAction::Set {
+            project,
+            package_manager,
+            output_dirs,
+            infer_output_dirs,
+            version_bump,
+            prerelease_id,
+            watch_include,
+            watch_exclude,
+            watch_debounce,
+        } => {
+            // Parsed up front so a typo changes nothing
+            let package_manager = package_manager.map(|name| parse_package_manager(&name));
+            let output_dirs: Vec<types::OutputDir> = output_dirs.iter().map(|spec| parse_output_dir(spec)).collect();
+            let version_bump = version_bump.map(|kind| parse_version_bump(&kind, prerelease_id.as_deref().unwrap_or_default()));
+            update_project_cli(&project, |p| {
+                let mut changes = Vec::new();
+                if let Some(manager) = package_manager {
+                    p.package_manager = manager;
+                    changes.push(match manager {
+                        Some(manager) => format!("package manager {}", manager),
+                        None => format!("package manager detected ({})", resolve_package_manager(p)),
+                    });
+                }
+                if infer_output_dirs {
+                    p.output_dirs.clear();
+                    changes.push(format!("build output from package.json ({})", format_output_dirs(&resolve_output_dirs(p))));
+                } else if !output_dirs.is_empty() {
+                    p.output_dirs = output_dirs;
+                    changes.push(format!("build output {}", format_output_dirs(&p.output_dirs)));
+                }
+                if let Some(bump) = version_bump {
+                    changes.push(format!("version bump {}", bump));
+                    p.version_bump = bump;
+                }
+                
+                let mut watch = p.watch.clone();
+                if !watch_include.is_empty() {
+                    watch.include = watch_include.into_iter().filter(|glob| !glob.trim().is_empty()).collect();
+                }
+                if !watch_exclude.is_empty() {
+                    watch.exclude = watch_exclude.into_iter().filter(|glob| !glob.trim().is_empty()).collect();
+                }
+                if let Some(debounce) = watch_debounce {
+                    watch.debounce_ms = debounce;
+                }
+                if watch != p.watch {
+                    validate_watch_settings(&watch)?;
+                    changes.push(format!(
+                        "watch include {} / exclude {} / debounce {}ms",
+                        watch.include.join(", "),
+                        watch.exclude.join(", "),
+                        watch.debounce_ms,
+                    ));
+                    p.watch = watch;
+                }
+                
+                if changes.is_empty() {
+                    return Err("Nothing to change; pass at least one setting".to_string());
+                }
+                Ok(format!("✅ Updated {}: {}", p.name, changes.join("; ")))
+            });
+        }
+        ProjectAction::Show { project } => {
+            let projects = load_projects();
+            match find_project(&projects, &project) {
+                Some(project) if json_output() => print_json(&mask_secret_env(project)),
+                Some(project) => print_project_details(project),
+                None => exit_project_not_found(&project),
+            }
+        }
+    }
+}
+
+fn commands_cli(action: CommandsAction) {
+    match action {
+        CommandsAction::Select { project, commands } => update_project_cli(&project, |p| {
+            let available = parse_package_json(&p.path);
+            if let Some(unknown) = commands.iter().find(|c| !available.contains(c)) {
+                return Err(format!("'{}' is not a script in {}/package.json (available: {})", unknown, p.path, available.join(", ")));
+            }
+            for command in &commands {
+                if !p.build_steps.iter().any(|step| step.script_name() == Some(command.as_str())) {
+                    p.build_steps.push(types::BuildStep::script(command));
+                }
+            }
+            p.build_commands = available;
+            Ok(format!("✅ Build commands: {}", format_selected_commands(p)))
+        }),
+        CommandsAction::Deselect { project, commands } => update_project_cli(&project, |p| {
+            if let Some(unknown) = commands.iter().find(|c| !p.build_steps.iter().any(|step| step.script_name() == Some(c.as_str()))) {
+                return Err(format!("'{}' is not selected", unknown));
+            }
+            p.build_steps.retain(|step| !step.script_name().is_some_and(|name| commands.iter().any(|c| c == name)));
+            Ok(format!("✅ Build commands: {}", format_selected_commands(p)))
+        }),
+        CommandsAction::Reorder { project, commands } => update_project_cli(&project, |p| {
+            let mut order = Vec::new();
+            for command in &commands {
+                let index = find_step_index(p, command)?;
+                if !order.contains(&index) {
+                    order.push(index);
+                }
+            }
+            let rest: Vec<usize> = (0..p.build_steps.len()).filter(|index| !order.contains(index)).collect();
+            order.extend(rest);
+            p.build_steps = order.into_iter().map(|index| p.build_steps[index].clone()).collect();
+            Ok(format!("✅ Build commands: {}", format_selected_commands(p)))
+        }),
+        CommandsAction::Timeout { project, step, seconds } => update_project_cli(&project, |p| {
+            let limit = (seconds > 0).then_some(seconds);
+            let subject = match step {
+                Some(step) => {
+                    let index = find_step_index(p, &step)?;
+                    p.build_steps[index].timeout_secs = limit;
+                    format!("'{}'", p.build_steps[index].kind)
+                }
+                None => {
+                    p.build_timeout_secs = limit;
+                    "the whole build".to_string()
+                }
+            };
+            Ok(match limit {
+                Some(limit) => format!("⏱️  Stopping {} after {}s", subject, limit),
+                None => format!("✅ No timeout for {}", subject),
+            })
+        }),
+        CommandsAction::List { project } => {
+            let projects = load_projects();
+            let Some(project) = find_project(&projects, &project) else {
+                exit_project_not_found(&project);
+            };
+            if json_output() {
+                print_json(&project.build_steps);
+            } else {
+                print_build_steps(project);
+            }
+        }
+        CommandsAction::Add { project, script, program, shell, timeout, args } => update_project_cli(&project, |p| {
+            let kind = match (script, program, shell) {
+                (Some(name), _, _) => {
+                    let available = parse_package_json(&p.path);
+                    if !available.contains(&name) {
+                        return Err(format!("'{}' is not a script in {}/package.json (available: {})", name, p.path, available.join(", ")));
+                    }
+                    types::StepKind::Script { name, args }
+                }
+                (_, Some(program), _) => types::StepKind::Command { program, args },
+                (_, _, Some(script)) if args.is_empty() => types::StepKind::Shell { script },
+                (_, _, Some(_)) => return Err("A shell step takes no separate arguments, put them in the snippet".to_string()),
+                (None, None, None) => return Err("Pass --script, --program or --shell".to_string()),
+            };
+            let step = types::BuildStep { kind, timeout_secs: timeout.filter(|seconds| *seconds > 0) };
+            let message = format!("✅ Added step {}: {}", p.build_steps.len() + 1, step_command_line(p, resolve_package_manager(p), &step));
+            p.build_steps.push(step);
+            Ok(message)
+        }),
+        CommandsAction::Remove { project, step } => update_project_cli(&project, |p| {
+            let index = find_step_index(p, &step)?;
+            let removed = p.build_steps.remove(index);
+            Ok(format!("🗑️  Removed step {}: {}", index + 1, removed.kind))
+        }),
+    }
+}
+
+// Steps are matched by position (from 1), script name or their short name
+fn find_step_index(project: &types::Project, identifier: &str) -> Result<usize, String> {
+    if let Ok(position) = identifier.parse::<usize>() {
+        if (1..=project.build_steps.len()).contains(&position) {
+            return Ok(position - 1);
+        }
+        return Err(format!("Project '{}' has {} build steps, there is no step {}", project.name, project.build_steps.len(), position));
+    }
+    project.build_steps.iter()
+        .position(|step| step.script_name() == Some(identifier))
+        .or_else(|| project.build_steps.iter().position(|step| step.kind.to_string() == identifier))
+        .ok_or_else(|| format!("'{}' is not a build step of '{}'", identifier, project.name))
+}
+
+fn print_build_steps(project: &types::Project) {
+    if project.build_steps.is_empty() {
+        println!("No build steps for '{}'", project.name);
+    }
+    let package_manager = resolve_package_manager(project);
+    let stale = stale_build_steps(project, &parse_package_json(&project.path));
+    for (index, step) in project.build_steps.iter().enumerate() {
+        let timeout = step.timeout_secs.map(|limit| format!(", timeout {}s", limit)).unwrap_or_default();
+        println!("{}. [{}{}] {}{}", index + 1, step.kind.type_name(), timeout, step_command_line(project, package_manager, step), stale_marker(&stale, step));
+    }
+    if let Some(limit) = project.build_timeout_secs {
+        println!("Build timeout: {}s", limit);
+    }
+}
+
+// Flags a script step whose script is gone from package.json
+fn stale_marker(stale: &[&str], step: &types::BuildStep) -> &'static str {
+    match step.script_name() {
+        Some(name) if stale.contains(&name) => "  ⚠️ not in package.json",
+        _ => "",
+    }
+}
+
+fn target_cli(action: TargetAction) {
+    match action {
+        TargetAction::Add { project, path, inactive, force } => update_project_cli(&project, |p| {
+            let path = absolute_path(&path);
+            if p.target_paths.iter().any(|t| t.path == path) {
+                return Err(format!("{} is already a target", path));
+            }
+            check_target_access(&path)?;
+            check_target_location(p, &path)?;
+            if !force {
+                check_target_package(p, &path).map_err(|e| format!("{} (use --force to add it anyway)", e))?;
+            }
+            let target = types::TargetPath {
+                id: uuid::Uuid::new_v4().to_string(),
+                path: path.clone(),
+                is_active: !inactive,
+                version_bump: None,
+                forced: force,
+            };
+            let mut flags = String::new();
+            if inactive {
+                flags.push_str(", inactive");
+            }
+            if force {
+                flags.push_str(", forced");
+            }
+            let message = format!("✅ Added target {} ({}{})", path, target.id, flags);
+            p.target_paths.push(target);
+            Ok(message)
+        }),
+        TargetAction::Remove { project, target } => update_project_cli(&project, |p| {
+            let index = find_target_index(p, &target)?;
+            let removed = p.target_paths.remove(index);
+            Ok(format!("🗑️  Removed target {}", removed.path))
+        }),
+        TargetAction::Enable { project, target } => update_project_cli(&project, |p| {
+            let index = find_target_index(p, &target)?;
+            p.target_paths[index].is_active = true;
+            Ok(format!("✅ Enabled target {}", p.target_paths[index].path))
+        }),
+        TargetAction::Disable { project, target } => update_project_cli(&project, |p| {
+            let index = find_target_index(p, &target)?;
+            p.target_paths[index].is_active = false;
+            Ok(format!("⏸️  Disabled target {}", p.target_paths[index].path))
+        }),
+        TargetAction::Force { project, target } => update_project_cli(&project, |p| {
+            let index = find_target_index(p, &target)?;
+            p.target_paths[index].forced = true;
+            Ok(format!("⚠️  Target {} is now updated without package checks", p.target_paths[index].path))
+        }),
+        TargetAction::Unforce { project, target } => update_project_cli(&project, |p| {
+            let index = find_target_index(p, &target)?;
+            p.target_paths[index].forced = false;
+            Ok(format!("✅ Target {} is checked before every update again", p.target_paths[index].path))
+        }),
+        TargetAction::Set { project, target, version_bump, prerelease_id } => {
+            let bump = match version_bump.trim() {
+                "default" => None,
+                kind => Some(parse_version_bump(kind, prerelease_id.as_deref().unwrap_or_default())),
+            };
+            update_project_cli(&project, |p| {
+                let index = find_target_index(p, &target)?;
+                p.target_paths[index].version_bump = bump.clone();
+                Ok(match bump {
+                    Some(bump) => format!("✅ Target {} now gets {} versions", p.target_paths[index].path, bump),
+                    None => format!("✅ Target {} follows the project's version bump ({})", p.target_paths[index].path, p.version_bump),
+                })
+            });
+        }
+        TargetAction::Find { project, roots, add, paths } => find_consumers_cli(&project, roots, add, paths),
+    }
+}
+
+fn env_cli(action: EnvAction) {
+    match action {
+        EnvAction::List { project } => {
+            let projects = load_projects();
+            let Some(project) = find_project(&projects, &project) else {
+                exit_project_not_found(&project);
+            };
+            let env = resolve_build_env(project).unwrap_or_else(|e| exit_cli_error(&e));
+            if json_output() {
+                print_json(&env.display());
+            } else if env.vars.is_empty() {
+                println!("No environment variables set for '{}'", project.name);
+            } else {
+                for var in env.display() {
+                    println!("{}", var);
+                }
+            }
+        }
+        EnvAction::Set { project, assignment, secret } => update_project_cli(&project, |p| {
+            let Some((key, value)) = assignment.split_once('=') else {
+                return Err(format!("Expected KEY=VALUE, got '{}'", assignment));
+            };
+            let key = key.trim().to_string();
+            validate_env_key(&key)?;
+            let var = types::EnvVar { key: key.clone(), value: value.to_string(), secret };
+            match p.env.iter_mut().find(|v| v.key == key) {
+                Some(existing) => *existing = var,
+                None => p.env.push(var),
+            }
+            Ok(format!("✅ Set {}{}", key, if secret { " (secret)" } else { "" }))
+        }),
+        EnvAction::Unset { project, key } => update_project_cli(&project, |p| {
+            let before = p.env.len();
+            p.env.retain(|v| v.key != key);
+            if p.env.len() == before {
+                return Err(format!("'{}' is not set", key));
+            }
+            Ok(format!("🗑️  Removed {}", key))
+        }),
+        EnvAction::AddFile { project, path, secret } => update_project_cli(&project, |p| {
+            if p.env_files.iter().any(|f| f.path == path) {
+                return Err(format!("{} is already loaded", path));
+            }
+            let file = types::EnvFile { path: path.clone(), secret };
+            let file_path = env_file_path(p, &file);
+            let content = std::fs::read_to_string(&file_path)
+                .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+            let vars = parse_env_file(&content).map_err(|e| format!("Invalid env file {}: {}", file_path.display(), e))?;
+            p.env_files.push(file);
+            Ok(format!("✅ Loading {} variables from {}", vars.len(), path))
+        }),
+        EnvAction::RemoveFile { project, path } => update_project_cli(&project, |p| {
+            let before = p.env_files.len();
+            p.env_files.retain(|f| f.path != path);
+            if p.env_files.len() == before {
+                return Err(format!("{} is not loaded", path));
+            }
+            Ok(format!("🗑️  No longer loading {}", path))
+        }),
+    }
+}
+
+fn export_cli(file: &str, identifiers: &[String], format: Option<&str>, include_secrets: bool) {
+    let projects = load_projects();
+    let selected: Vec<types::Project> = if identifiers.is_empty() {
+        projects.clone()
+    } else {
+        identifiers.iter()
+            .map(|identifier| match find_project(&projects, identifier) {
+                Some(project) => project.clone(),
+                None => exit_project_not_found(identifier),
+            })
+            .collect()
+    };
+    
+    let path = std::path::PathBuf::from(absolute_path(file));
+    let format = parse_bundle_format(format, &path);
+    if let Err(e) = write_project_bundle(&path, &selected, format, include_secrets) {
+        exit_cli_error(&e);
+    }
+    
+    if json_output() {
+        print_json(&serde_json::json!({
+            "file": path,
+            "format": format,
+            "projects": selected.iter().map(|p| &p.name).collect::<Vec<_>>(),
+        }));
+    } else {
+        println!("✅ Exported {} projects to {}", selected.len(), path.display());
+    }
+}
+
+fn import_cli(file: &str, on_conflict: &str, remaps: &[String], format: Option<&str>) {
+    let on_conflict = match types::ConflictPolicy::from_name(on_conflict) {
+        Some(policy) => policy,
+        None => exit_cli_error(&format!("Unknown conflict policy '{}'. Use skip, overwrite or rename", on_conflict)),
+    };
+    let remaps: Vec<types::PathRemap> = remaps.iter()
+        .map(|remap| parse_path_remap(remap).unwrap_or_else(|e| exit_cli_error(&e)))
+        .collect();
+    
+    let path = std::path::PathBuf::from(absolute_path(file));
+    let format = parse_bundle_format(format, &path);
+    let incoming = match read_project_bundle(&path, format) {
+        Ok(projects) => projects,
+        Err(e) => exit_cli_error(&e),
+    };
+    let outcomes = match import_projects(incoming, on_conflict, &remaps) {
+        Ok(outcomes) => outcomes,
+        Err(e) => exit_cli_error(&e),
+    };
+    
+    if json_output() {
+        let outcomes: Vec<types::ImportOutcome> = outcomes.into_iter()
+            .map(|outcome| types::ImportOutcome { project: mask_secret_env(&outcome.project), ..outcome })
+            .collect();
+        print_json(&outcomes);
+        return;
+    }
+    for outcome in &outcomes {
+        let project = &outcome.project;
+        match outcome.action {
+            types::ImportAction::Added => println!("✅ Added {} ({})", project.name, project.path),
+            types::ImportAction::Skipped => println!("⏭️  Skipped {}, it already exists", project.name),
+            types::ImportAction::Overwritten => println!("♻️  Overwrote {} ({})", project.name, project.path),
+            types::ImportAction::Renamed => println!("✅ Added {} under a new name ({})", project.name, project.path),
+        }
+        if !std::path::Path::new(&project.path).exists() {
+            println!("   ⚠️  {} does not exist on this machine; remap it with --remap FROM=TO", project.path);
+        }
+        if outcome.action != types::ImportAction::Skipped {
+            for var in project.env.iter().filter(|var| var.secret && var.value.is_empty()) {
+                println!("   ⚠️  Secret {} was not in the bundle; set it with `env set --secret`", var.key);
+            }
+        }
+    }
+    let imported = outcomes.iter().filter(|o| o.action != types::ImportAction::Skipped).count();
+    println!("Imported {} of {} projects.", imported, outcomes.len());
+}
+
+fn parse_bundle_format(format: Option<&str>, path: &std::path::Path) -> types::BundleFormat {
+    match format.map(|f| f.trim().to_lowercase()).as_deref() {
+        None => bundle_format_for(path),
+        Some("json") => types::BundleFormat::Json,
+        Some("toml") => types::BundleFormat::Toml,
+        Some(other) => exit_cli_error(&format!("Unknown bundle format '{}'. Use json or toml", other)),
+    }
+}
+
+fn profile_cli(action: ProfileAction) {
+    match action {
+        ProfileAction::List => {
+            let active = active_profile();
+            let profiles = list_profiles();
+            if json_output() {
+                print_json(&serde_json::json!({
+                    "active": active,
+                    "profiles": profiles,
+                    "data_dir": get_data_dir(),
+                    "data_dir_source": data_dir_source().to_string(),
+                }));
+                return;
+            }
+            println!("Profiles in {} ({}):", get_data_dir().display(), data_dir_source());
+            for profile in profiles {
+                let marker = if profile == active { "▶" } else { " " };
+                println!("{} {}", marker, profile);
+            }
+        }
+        ProfileAction::Create { name } => match create_profile(&name) {
+            Ok(()) if json_output() => print_json(&serde_json::json!({ "created": name })),
+            Ok(()) => println!("✅ Created profile {} (use it with `profile use {}` or --profile {})", name, name, name),
+            Err(e) => exit_cli_error(&e),
+        },
+        ProfileAction::Use { name } => match switch_profile(&name) {
+            Ok(()) if json_output() => print_json(&serde_json::json!({ "active": name })),
+            Ok(()) => println!("✅ Now using profile {}", name),
+            Err(e) => exit_cli_error(&e),
+        },
+    }
+}
+
+fn find_consumers_cli(project_identifier: &str, roots: Vec<String>, add: bool, paths: Vec<String>) {
+    let projects = load_projects();
+    let project = match find_project(&projects, project_identifier) {
+        Some(project) => project.clone(),
+        None => exit_project_not_found(project_identifier),
+    };
+    let roots = if roots.is_empty() {
+        consumer_search_roots(&project)
+    } else {
+        roots.iter().map(|root| absolute_path(root)).collect()
+    };
+    
+    status_line(&format!("🔍 Searching {} for {}...", roots.join(", "), project.name));
+    let mut candidates = match find_consumers(&project, &roots) {
+        Ok(candidates) => candidates,
+        Err(e) => exit_cli_error(&e),
+    };
+    
+    if add {
+        // Like the GUI checkboxes, only the chosen copies are added; guessing which of
+        // several copies is meant could point builds at the wrong project
+        let selected = match select_consumers(&candidates, &paths) {
+            Ok(selected) => selected,
+            Err(e) => exit_cli_error(&e),
+        };
+        let added = update_projects(|projects| {
+            let stored = projects.iter_mut()
+                .find(|p| p.id == project.id)
+                .ok_or_else(|| format!("Project '{}' no longer exists", project.name))?;
+            for &index in &selected {
+                let candidate = &mut candidates[index];
+                stored.target_paths.push(types::TargetPath {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    path: candidate.path.clone(),
+                    is_active: true,
+                    version_bump: None,
+                    forced: false,
+                });
+                candidate.is_target = true;
+                status_line(&format!("✅ Added target {}", candidate.path));
+            }
+            Ok(())
+        });
+        if let Err(e) = added {
+            exit_cli_error(&e);
+        }
+    }
+    
+    if json_output() {
+        print_json(&candidates);
+        return;
+    }
+    if candidates.is_empty() {
+        println!("No installed copies of {} found.", project.name);
+        return;
+    }
+    println!("Installed copies of {}:", project.name);
+    for candidate in &candidates {
+        let marker = if candidate.is_target { "🎯" } else { "📦" };
+        println!("{} v{}  {}", marker, candidate.installed_version.as_deref().unwrap_or("?"), candidate.path);
+    }
+    if !add && candidates.iter().any(|c| !c.is_target) {
+        println!("Add them as targets with `target find --project \"{}\" --add --path <copy>`.", project.name);
+    }
+}
+
+// Indexes of the copies `target find --add` should add: the ones named with --path
+// (the copy itself or the project holding it), or the only new copy when none are named
+fn select_consumers(candidates: &[types::ConsumerCandidate], paths: &[String]) -> Result<Vec<usize>, String> {
+    let new: Vec<usize> = (0..candidates.len()).filter(|&i| !candidates[i].is_target).collect();
+    if paths.is_empty() {
+        if new.len() > 1 {
+            let listed = new.iter().map(|&i| format!("  {}", candidates[i].path)).collect::<Vec<_>>().join("\n");
+            return Err(format!("Found {} copies; choose the ones to add with --path:\n{}", new.len(), listed));
+        }
+        return Ok(new);
+    }
+    
+    let mut selected = Vec::new();
+    for path in paths {
+        let absolute = absolute_path(path);
+        let matches: Vec<usize> = (0..candidates.len())
+            .filter(|&i| [&candidates[i].path, &candidates[i].consumer_path].iter().any(|p| **p == *path || **p == absolute))
+            .collect();
+        if matches.is_empty() {
+            return Err(format!("No copy found at '{}'", path));
+        }
+        for index in matches {
+            if new.contains(&index) && !selected.contains(&index) {
+                selected.push(index);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+// Apply one change to a project with the store locked
+// The change returns the message to print, or an error that leaves the store untouched
+fn update_project_cli(project_identifier: &str, change: impl FnOnce(&mut types::Project) -> Result<String, String>) {
+    let result = update_projects(|projects| {
+        let Some(id) = find_project(projects, project_identifier).map(|p| p.id.clone()) else {
+            return Ok(None);
+        };
+        let project = projects.iter_mut().find(|p| p.id == id).expect("project was just found");
+        let message = change(project)?;
+        Ok(Some((message, project.clone())))
+    });
+    
+    match result {
+        // Scripts get the updated project back
+        Ok(Some((_, updated))) if json_output() => print_json(&mask_secret_env(&updated)),
+        Ok(Some((message, _))) => println!("{}", message),
+        Ok(None) => exit_project_not_found(project_identifier),
+        Err(e) => exit_cli_error(&e),
+    }
+}
+
+// Targets are matched by ID, exact path or the path as typed on the command line
+fn find_target_index(project: &types::Project, identifier: &str) -> Result<usize, String> {
+    let absolute = absolute_path(identifier);
+    project.target_paths.iter()
+        .position(|t| t.id == identifier || t.path == identifier || t.path == absolute)
+        .ok_or_else(|| format!("Project '{}' has no target '{}'", project.name, identifier))
+}
+
+// Paths typed on the command line are stored absolute, like the ones picked in the GUI
+fn absolute_path(path: &str) -> String {
+    let path = std::path::Path::new(path.trim());
+    std::fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string_lossy().to_string())
+}
+
+fn parse_build_scope(name: &str) -> types::BuildScope {
+    match types::BuildScope::from_name(name) {
+        Some(scope) => scope,
+        None => exit_cli_error(&format!("Unknown scope '{}'. Use package or workspace", name)),
+    }
+}
+
+// None stands for auto-detection
+fn parse_package_manager(name: &str) -> Option<types::PackageManager> {
+    if name.trim().eq_ignore_ascii_case("auto") {
+        return None;
+    }
+    match types::PackageManager::from_name(name) {
+        Some(manager) => Some(manager),
+        None => exit_cli_error(&format!("Unknown package manager '{}'. Use npm, pnpm, yarn, bun or auto", name)),
+    }
+}
+
+// "lib" or "dist:lib", relative to the project and the target
+fn parse_output_dir(spec: &str) -> types::OutputDir {
+    let (source, destination) = spec.split_once(':').unwrap_or((spec, spec));
+    let clean = |path: &str| path.trim().trim_matches(['/', '\\']).to_string();
+    let (source, destination) = (clean(source), clean(destination));
+    if source.is_empty() || destination.is_empty() || std::path::Path::new(&source).is_absolute() {
+        exit_cli_error(&format!("Invalid output folder '{}', expected a relative SOURCE or SOURCE:DESTINATION", spec));
+    }
+    types::OutputDir { source, destination }
+}
+
+fn parse_version_bump(kind: &str, identifier: &str) -> types::VersionBump {
+    match types::VersionBump::from_kind(&kind.trim().to_lowercase().replace('-', "_"), identifier) {
+        Some(bump) => bump,
+        None => exit_cli_error(&format!("Unknown version bump '{}'. Use {}", kind, types::VersionBump::KINDS.join(", "))),
+    }
+}
+
+fn exit_cli_error(error: &str) -> ! {
+    if json_output() {
+        print_json(&serde_json::json!({ "error": error.trim_end_matches('.') }));
+    } else {
+        println!("❌ Error: {}.", error.trim_end_matches('.'));
+    }
+    process::exit(1);
+}
+
+fn json_output() -> bool {
+    OUTPUT_FORMAT.get() == Some(&OutputFormat::Json)
+}
+
+fn print_json(value: &impl serde::Serialize) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize output: {}", e);
             process::exit(1);
         }
     }
 }
 
+// Warnings of a build about to start, e.g. selected scripts gone from package.json;
+// a plan that fails is reported by the build itself
+fn print_plan_warnings(plan: Result<types::BuildPlan, String>) {
+    for warning in plan.map(|plan| plan.warnings).unwrap_or_default() {
+        status_line(&format!("⚠️  {}", warning));
+    }
+}
+
+// Progress messages that are not part of the result; kept off stdout in JSON mode
+fn status_line(message: &str) {
+    if json_output() {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+// Structured result of one build for `--output json`
+fn build_result_json(result: &Result<String, String>, record: Option<&types::BuildRecord>) -> serde_json::Value {
+    let Some(record) = record else {
+        // The build never started, e.g. no targets configured or a cascade project
+        // passed over for having no build steps
+        return serde_json::json!({
+            "success": result.is_ok(),
+            "message": result.as_ref().ok(),
+            "error": result.as_ref().err(),
+        });
+    };
+    
+    serde_json::json!({
+        "success": result.is_ok(),
+        "build_id": record.id,
+        "project_id": record.project_id,
+        "project_name": record.project_name,
+        "status": record.status,
+        "started_at": record.started_at,
+        "finished_at": record.finished_at,
+        "duration_ms": record.duration_ms,
+        "package_manager": record.package_manager,
+        "exit_code": record.exit_code,
+        "commands": record.command_outcomes,
+        "targets": record.targets,
+        "error": record.error,
+        "log_file": get_build_record_file(record),
+    })
+}
+
+fn format_selected_commands(project: &types::Project) -> String {
+    if project.build_steps.is_empty() {
+        "(none selected)".to_string()
+    } else {
+        project.build_steps.iter().map(|step| step.kind.to_string()).collect::<Vec<_>>().join(" → ")
+    }
+}
+
+fn print_project_details(project: &types::Project) {
+    println!("📦 {} ({})", project.name, project.id);
+    println!("   Path: {}", project.path);
+    let package_manager = match project.package_manager {
+        Some(manager) => manager.to_string(),
+        None => format!("{} (detected)", resolve_package_manager(project)),
+    };
+    println!("   Package manager: {}", package_manager);
+    if let Some(link) = &project.workspace {
+        println!("   Workspace: {} (package {}, {} scope)", link.root, link.package_name, link.scope);
+    }
+    println!("   Version bump: {}", project.version_bump);
+    println!("   Build output: {}", format_output_dirs(&resolve_output_dirs(project)));
+    println!("   Build commands: {}", format_selected_commands(project));
+    let package_manager = resolve_package_manager(project);
+    let stale = stale_build_steps(project, &parse_package_json(&project.path));
+    for (index, step) in project.build_steps.iter().enumerate() {
+        let timeout = step.timeout_secs.map(|limit| format!(" (timeout {}s)", limit)).unwrap_or_default();
+        println!("     {}. {}{}{}", index + 1, step_command_line(project, package_manager, step), timeout, stale_marker(&stale, step));
+    }
+    if let Some(limit) = project.build_timeout_secs {
+        println!("   Build timeout: {}s", limit);
+    }
+    println!("   Available scripts: {}", parse_package_json(&project.path).join(", "));
+    println!("   Watch: include {} / exclude {}", project.watch.include.join(", "), project.watch.exclude.join(", "));
+    for file in &project.env_files {
+        println!("   Env file: {}{}", file.path, if file.secret { " (secret)" } else { "" });
+    }
+    for var in &project.env {
+        let value = if var.secret { SECRET_MASK } else { var.value.as_str() };
+        println!("   Env: {}={}", var.key, value);
+    }
+    let projects = load_projects();
+    let names = |list: Vec<&types::Project>| list.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(", ");
+    let feeds = names(downstream_projects(&projects, project));
+    let fed_by = names(upstream_projects(&projects, project));
+    if !feeds.is_empty() {
+        println!("   Feeds: {}", feeds);
+    }
+    if !fed_by.is_empty() {
+        println!("   Fed by: {}", fed_by);
+    }
+    println!("   Targets:");
+    if project.target_paths.is_empty() {
+        println!("     (none)");
+    }
+    for target in &project.target_paths {
+        let state = if target.is_active { "✅" } else { "⏸️ " };
+        let version = get_package_version(&target.path).unwrap_or_else(|| "?".to_string());
+        println!("     {} {} (v{})", state, target.path, version);
+        println!("        ID: {}", target.id);
+        if let Some(bump) = &target.version_bump {
+            println!("        Version bump: {}", bump);
+        }
+        if target.forced {
+            println!("        Forced: updated even if its package name does not match");
+        }
+        if let Err(e) = validate_target(project, &target.path, target.forced) {
+            println!("        ⚠️  {}", e);
+        }
+    }
+}
+
+fn format_output_dirs(output_dirs: &[types::OutputDir]) -> String {
+    output_dirs.iter()
+        .map(|dir| {
+            if dir.source == dir.destination {
+                dir.source.clone()
+            } else {
+                format!("{} → {}", dir.source, dir.destination)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,234 @@
+use dioxus::prelude::*;
+use crate::components::BuildLogView;
+use crate::types::{BuildRecord, BuildStatus};
+use crate::logic::*;
+
+/// Build history page - past runs of a project
+#[component]
+pub fn ProjectHistory(id: String) -> Element {
+    let project = load_projects().into_iter().find(|p| p.id == id);
+    let history_id = id.clone();
+    let records = use_signal(move || load_build_history(&history_id));
+    let mut status_filter = use_signal(|| None::<BuildStatus>);
+    let mut search_query = use_signal(String::new);
+    let mut selected_record = use_signal(|| None::<BuildRecord>);
+    let mut rollback_messages = use_signal(Vec::<Result<String, String>>::new);
+
+    // Snapshots taken by the build open in the detail modal
+    let backups = selected_record()
+        .map(|r| list_build_backups(&r.project_id, &r.id))
+        .unwrap_or_default();
+    let filtered = filter_build_history(&records(), status_filter(), &search_query());
+    let project_name = project.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| "Unknown project".to_string());
+
+    rsx! {
+        div { class: "min-h-screen bg-gray-50 p-6",
+            // Header
+            div { class: "max-w-4xl mx-auto mb-8",
+                Link {
+                    to: crate::Route::ProjectDetail { id: id.clone() },
+                    class: "text-blue-600 hover:text-blue-800 mb-2 inline-block",
+                    "← Back to Project"
+                }
+                h1 { class: "text-3xl font-bold text-gray-900", "Build History" }
+                p { class: "text-gray-600 mt-1", "{project_name}" }
+            }
+
+            div { class: "max-w-4xl mx-auto space-y-4",
+                // Filters
+                div { class: "bg-white rounded-lg shadow-md p-4 flex space-x-3",
+                    select {
+                        class: "px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        value: status_filter().map(|s| s.as_str()).unwrap_or("all"),
+                        onchange: move |e| status_filter.set(BuildStatus::from_name(&e.value())),
+                        option { value: "all", "All statuses" }
+                        for status in BuildStatus::ALL {
+                            option { value: status.as_str(), "{status.icon()} {status}" }
+                        }
+                    }
+                    input {
+                        class: "flex-1 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        r#type: "text",
+                        placeholder: "Search commands, targets, versions or log output",
+                        value: search_query(),
+                        oninput: move |e| search_query.set(e.value()),
+                    }
+                }
+
+                if records().is_empty() {
+                    div { class: "text-center py-12",
+                        p { class: "text-gray-500", "No builds recorded yet" }
+                    }
+                } else if filtered.is_empty() {
+                    div { class: "text-center py-12",
+                        p { class: "text-gray-500", "No builds match the current filters" }
+                    }
+                } else {
+                    for record in filtered.into_iter() {
+                        div {
+                            class: "bg-white rounded-lg shadow-md p-4 cursor-pointer hover:shadow-lg transition-shadow",
+                            onclick: {
+                                let record = record.clone();
+                                move |_| {
+                                    rollback_messages.set(Vec::new());
+                                    selected_record.set(Some(record.clone()));
+                                }
+                            },
+                            div { class: "flex items-center justify-between",
+                                div { class: "flex items-center space-x-3",
+                                    span { class: "text-xl", "{record.status.icon()}" }
+                                    div {
+                                        div { class: "font-medium text-gray-900",
+                                            {record.started_at.format("%Y-%m-%d %H:%M:%S").to_string()}
+                                        }
+                                        div { class: "text-xs text-gray-500 mt-1",
+                                            "{record.package_manager} · {record.commands.join(\" → \")}"
+                                        }
+                                    }
+                                }
+                                div { class: "text-right",
+                                    div { class: "text-sm text-gray-700", "{record.status}" }
+                                    div { class: "text-xs text-gray-500", "⏱️ {format_duration_ms(record.duration_ms)}" }
+                                }
+                            }
+                            if !record.targets.is_empty() {
+                                div { class: "mt-2 flex flex-wrap gap-2",
+                                    for target in record.targets.iter() {
+                                        span {
+                                            class: if target.error.is_none() { "text-xs bg-green-100 text-green-800 px-2 py-1 rounded" } else { "text-xs bg-red-100 text-red-800 px-2 py-1 rounded" },
+                                            "{extract_project_name(&target.path)}"
+                                            if let Some(new_version) = &target.new_version {
+                                                " v{new_version}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Record detail modal
+            if let Some(record) = selected_record() {
+                div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                    onclick: move |_| selected_record.set(None),
+                    div {
+                        class: "bg-white rounded-lg p-6 w-full max-w-3xl mx-4 max-h-[90vh] overflow-y-auto",
+                        onclick: move |e| e.stop_propagation(),
+                        h2 { class: "text-xl font-semibold mb-1", "{record.status.icon()} Build {record.status}" }
+                        p { class: "text-sm text-gray-500 mb-4",
+                            {format!(
+                                "{} → {} ({})",
+                                record.started_at.format("%Y-%m-%d %H:%M:%S"),
+                                record.finished_at.format("%H:%M:%S"),
+                                format_duration_ms(record.duration_ms),
+                            )}
+                        }
+                        div { class: "space-y-4",
+                            div {
+                                h3 { class: "font-medium text-gray-800 mb-1", "Commands ({record.package_manager})" }
+                                ol { class: "list-decimal list-inside text-sm text-gray-700",
+                                    if record.command_outcomes.is_empty() {
+                                        for cmd in record.commands.iter() {
+                                            li { "{cmd}" }
+                                        }
+                                    }
+                                    for outcome in record.command_outcomes.iter() {
+                                        li {
+                                            "{outcome.command}"
+                                            span { class: "text-xs text-gray-500 ml-2", "{describe_command_outcome(outcome)}" }
+                                        }
+                                    }
+                                }
+                                if let Some(code) = record.exit_code {
+                                    p { class: "text-xs text-gray-500 mt-1", "Exit code: {code}" }
+                                }
+                                if let Some(hit) = &record.timeout {
+                                    p { class: "text-xs text-orange-700 mt-1", "⏱️ {hit}" }
+                                }
+                            }
+                            if let Some(error) = &record.error {
+                                div { class: "bg-red-50 border border-red-200 rounded-md p-3",
+                                    pre { class: "text-sm text-red-800 whitespace-pre-wrap", "{error}" }
+                                }
+                            }
+                            if !record.targets.is_empty() {
+                                div {
+                                    h3 { class: "font-medium text-gray-800 mb-1", "Targets" }
+                                    div { class: "space-y-2",
+                                        for target in record.targets.iter() {
+                                            div { class: "p-2 border border-gray-200 rounded text-sm",
+                                                div { class: "font-medium text-gray-900", "{extract_project_name(&target.path)}" }
+                                                div { class: "text-xs text-gray-500", "{target.path}" }
+                                                div { class: "text-xs mt-1",
+                                                    match (&target.old_version, &target.new_version) {
+                                                        (Some(old), Some(new)) => rsx! {
+                                                            span { class: "text-blue-700", "v{old} → v{new}" }
+                                                            span { class: "text-gray-500 ml-2", "{target.files}" }
+                                                        },
+                                                        (Some(old), None) => rsx! { span { class: "text-gray-600", "v{old} (not changed)" } },
+                                                        _ => rsx! {},
+                                                    }
+                                                }
+                                                if let Some(error) = &target.error {
+                                                    div { class: "text-xs text-red-600 mt-1", "{error}" }
+                                                }
+                                                if let Some(backup) = backups.iter().find(|b| b.target_id == target.target_id).cloned() {
+                                                    button {
+                                                        class: "mt-2 px-3 py-1 text-xs bg-yellow-100 text-yellow-800 hover:bg-yellow-200 rounded transition-colors",
+                                                        onclick: move |_| rollback_messages.set(vec![rollback_target(&backup)]),
+                                                        "↺ Roll back this target"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if !record.log.is_empty() {
+                                div {
+                                    h3 { class: "font-medium text-gray-800 mb-1", "Log" }
+                                    BuildLogView { lines: record.log.clone() }
+                                }
+                            }
+                        }
+                        if !rollback_messages().is_empty() {
+                            div { class: "mt-4 space-y-1",
+                                for message in rollback_messages().iter() {
+                                    match message {
+                                        Ok(text) => rsx! { p { class: "text-sm text-green-700", "{text}" } },
+                                        Err(text) => rsx! { p { class: "text-sm text-red-700", "❌ {text}" } },
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex justify-end space-x-3 mt-4",
+                            if !backups.is_empty() {
+                                button {
+                                    class: "px-4 py-2 bg-yellow-500 hover:bg-yellow-600 text-white rounded-md transition-colors",
+                                    onclick: {
+                                        let project_id = record.project_id.clone();
+                                        let build_id = record.id.clone();
+                                        move |_| {
+                                            match rollback_build(&project_id, &build_id) {
+                                                Ok(messages) => rollback_messages.set(messages),
+                                                Err(e) => rollback_messages.set(vec![Err(e)]),
+                                            }
+                                        }
+                                    },
+                                    "↺ Roll back whole build"
+                                }
+                            }
+                            button {
+                                class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                onclick: move |_| selected_record.set(None),
+                                "Close"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use crate::logic::*;
 use crate::components::ProjectCard;
+use crate::types::{BuildScope, BundleFormat, ConflictPolicy, ImportAction, Project, WorkspacePackage};
 use crate::Route;
 
 
@@ -9,9 +10,74 @@ use crate::Route;
 #[component]
 pub fn Home() -> Element {
     let mut projects = use_signal(|| load_projects());
+    let store_revision = use_context::<crate::StoreRevision>().0;
+    let mut store_file = use_context::<crate::StoreFile>().0;
+    let mut profiles = use_signal(list_profiles);
+    let mut current_profile = use_signal(active_profile);
+    let mut show_profile_modal = use_signal(|| false);
+    let mut new_profile_name = use_signal(String::new);
+    let mut profile_error = use_signal(|| None::<String>);
+    let mut show_export_modal = use_signal(|| false);
+    let mut export_selection = use_signal(Vec::<String>::new); // IDs of the projects to export
+    let mut export_format = use_signal(|| BundleFormat::Json);
+    let mut export_result = use_signal(|| None::<Result<String, String>>);
+    let mut export_secrets = use_signal(|| false);
+    let mut show_import_modal = use_signal(|| false);
+    let mut import_file = use_signal(String::new);
+    let mut import_bundle = use_signal(|| None::<Result<Vec<Project>, String>>);
+    let mut import_policy = use_signal(ConflictPolicy::default);
+    let mut import_remaps = use_signal(String::new); // One FROM=TO per line
+    let mut import_result = use_signal(|| None::<Result<String, String>>);
     let mut show_add_modal = use_signal(|| false);
     let mut new_project_name = use_signal(|| String::new());
     let mut new_project_path = use_signal(|| String::new());
+    let mut show_workspace_modal = use_signal(|| false);
+    let mut workspace_root = use_signal(String::new);
+    let mut workspace_packages = use_signal(|| None::<Result<Vec<WorkspacePackage>, String>>);
+    let mut selected_packages = use_signal(Vec::<String>::new); // Paths of the packages to import
+    let mut workspace_scope = use_signal(BuildScope::default);
+    
+    // Look for workspace packages below the chosen root, preselecting the new ones
+    let mut scan_workspace = move || {
+        let found = find_workspace_packages(workspace_root().trim());
+        if let Ok(packages) = &found {
+            let existing = projects();
+            selected_packages.set(packages.iter()
+                .filter(|p| !existing.iter().any(|e| e.path == p.path))
+                .map(|p| p.path.clone())
+                .collect());
+        }
+        workspace_packages.set(Some(found));
+    };
+
+    // Make a profile the active one and load its projects
+    let mut use_profile = move |name: String| {
+        match switch_profile(&name) {
+            Ok(()) => {
+                current_profile.set(name);
+                store_file.set(get_projects_file());
+                projects.set(load_projects());
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    };
+
+    let mut close_import = move || {
+        show_import_modal.set(false);
+        import_file.set(String::new());
+        import_bundle.set(None);
+        import_remaps.set(String::new());
+        import_result.set(None);
+    };
+
+    // Reload when the store changes on disk, e.g. a project added from the CLI
+    use_effect(move || {
+        store_revision();
+        let stored = load_projects();
+        if stored != *projects.peek() {
+            projects.set(stored);
+        }
+    });
 
     rsx! {
         div { class: "min-h-screen bg-gray-50 p-6",
@@ -25,11 +91,48 @@ pub fn Home() -> Element {
                         }
                     }
                     div { class: "flex space-x-3",
+                        // Profile switcher, each profile has its own projects
+                        select {
+                            class: "px-3 py-2 border border-gray-300 rounded-lg bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            title: "Configuration profile",
+                            value: current_profile(),
+                            onchange: move |e| {
+                                if e.value() == "__new__" {
+                                    show_profile_modal.set(true);
+                                } else {
+                                    use_profile(e.value());
+                                }
+                            },
+                            for profile in profiles() {
+                                option { value: "{profile}", selected: profile == current_profile(), "👤 {profile}" }
+                            }
+                            option { value: "__new__", "+ New profile..." }
+                        }
                         button {
                             class: "bg-blue-600 hover:bg-blue-700 text-white px-4 py-2 rounded-lg flex items-center space-x-2 transition-colors",
                             onclick: move |_| show_add_modal.set(true),
                             span { "+ Add Project" }
                         }
+                        button {
+                            class: "bg-white border border-gray-300 hover:bg-gray-100 text-gray-800 px-4 py-2 rounded-lg flex items-center space-x-2 transition-colors",
+                            onclick: move |_| show_workspace_modal.set(true),
+                            span { "🧩 Import Workspace" }
+                        }
+                        button {
+                            class: "bg-white border border-gray-300 hover:bg-gray-100 text-gray-800 px-4 py-2 rounded-lg flex items-center space-x-2 transition-colors",
+                            onclick: move |_| show_import_modal.set(true),
+                            span { "📥 Import" }
+                        }
+                        button {
+                            class: "bg-white border border-gray-300 hover:bg-gray-100 text-gray-800 px-4 py-2 rounded-lg flex items-center space-x-2 transition-colors",
+                            disabled: projects().is_empty(),
+                            onclick: move |_| {
+                                export_selection.set(projects().iter().map(|p| p.id.clone()).collect());
+                                export_result.set(None);
+                                show_export_modal.set(true);
+                            },
+                            span { "📤 Export" }
+                        }
                         Link {
                             to: Route::Settings {},
                             class: "bg-gray-600 hover:bg-gray-700 text-white px-4 py-2 rounded-lg flex items-center space-x-2 transition-colors",
@@ -140,10 +243,13 @@ pub fn Home() -> Element {
                                         new_project_name().trim().to_string(),
                                         new_project_path().trim().to_string(),
                                     );
-                                    let mut current_projects = projects();
-                                    current_projects.push(project);
-                                    save_projects(&current_projects);
-                                    projects.set(current_projects);
+                                    if let Err(e) = update_projects(|stored| {
+                                        stored.push(project);
+                                        Ok(())
+                                    }) {
+                                        eprintln!("{}", e);
+                                    }
+                                    projects.set(load_projects());
 
                                     show_add_modal.set(false);
                                     new_project_name.set(String::new());
@@ -155,6 +261,405 @@ pub fn Home() -> Element {
                     }
                 }
             }
+
+            // Export Modal
+            if show_export_modal() {
+                div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                    div { class: "bg-white rounded-lg p-6 w-full max-w-2xl mx-4 max-h-[90vh] overflow-y-auto",
+                        h2 { class: "text-xl font-semibold mb-1", "Export Projects" }
+                        p { class: "text-sm text-gray-600 mb-4",
+                            "Writes the selected project definitions to a bundle teammates can import."
+                        }
+                        div { class: "space-y-2",
+                            for project in projects() {
+                                {
+                                    let id = project.id.clone();
+                                    rsx! {
+                                        label { class: "flex items-center space-x-3 p-2 border border-gray-200 rounded",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: export_selection().contains(&project.id),
+                                                onchange: move |_| {
+                                                    let mut selected = export_selection();
+                                                    if let Some(index) = selected.iter().position(|s| s == &id) {
+                                                        selected.remove(index);
+                                                    } else {
+                                                        selected.push(id.clone());
+                                                    }
+                                                    export_selection.set(selected);
+                                                },
+                                            }
+                                            div { class: "flex-1 min-w-0",
+                                                div { class: "font-medium text-gray-900", "{project.name}" }
+                                                div { class: "text-xs text-gray-500 truncate", "{project.path}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "mt-4",
+                            label { class: "block text-sm font-medium text-gray-700 mb-1", "Format" }
+                            select {
+                                class: "w-full px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                onchange: move |e| export_format.set(if e.value() == "toml" { BundleFormat::Toml } else { BundleFormat::Json }),
+                                option { value: "json", selected: export_format() == BundleFormat::Json, "JSON" }
+                                option { value: "toml", selected: export_format() == BundleFormat::Toml, "TOML" }
+                            }
+                        }
+                        label { class: "flex items-center space-x-2 mt-4 text-sm text-gray-700",
+                            input {
+                                r#type: "checkbox",
+                                checked: export_secrets(),
+                                onchange: move |e| export_secrets.set(e.checked()),
+                            }
+                            span { "Include secret variable values (masked otherwise)" }
+                        }
+                        if let Some(ref result) = export_result() {
+                            match result {
+                                Ok(msg) => rsx! { p { class: "text-sm text-green-600 mt-3", "{msg}" } },
+                                Err(msg) => rsx! { p { class: "text-sm text-red-600 mt-3", "{msg}" } },
+                            }
+                        }
+                        div { class: "flex justify-end space-x-3 mt-6",
+                            button {
+                                class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                onclick: move |_| show_export_modal.set(false),
+                                "Close"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                disabled: export_selection().is_empty(),
+                                onclick: move |_| {
+                                    let format = export_format();
+                                    let include_secrets = export_secrets();
+                                    let selected: Vec<Project> = projects().into_iter()
+                                        .filter(|p| export_selection().contains(&p.id))
+                                        .collect();
+                                    spawn(async move {
+                                        let file_name = match format {
+                                            BundleFormat::Json => "projects.json",
+                                            BundleFormat::Toml => "projects.toml",
+                                        };
+                                        if let Some(path) = save_bundle_dialog(file_name).await {
+                                            export_result.set(Some(
+                                                write_project_bundle(std::path::Path::new(&path), &selected, format, include_secrets)
+                                                    .map(|_| format!("✅ Exported {} projects to {}", selected.len(), path)),
+                                            ));
+                                        }
+                                    });
+                                },
+                                "Export {export_selection().len()} Projects..."
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Import Modal
+            if show_import_modal() {
+                div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                    div { class: "bg-white rounded-lg p-6 w-full max-w-2xl mx-4 max-h-[90vh] overflow-y-auto",
+                        h2 { class: "text-xl font-semibold mb-1", "Import Projects" }
+                        p { class: "text-sm text-gray-600 mb-4",
+                            "Adds the projects from an exported bundle (JSON or TOML) or another projects.json."
+                        }
+                        div { class: "flex space-x-2",
+                            input {
+                                class: "flex-1 px-3 py-2 border border-gray-300 rounded-md bg-gray-50",
+                                r#type: "text",
+                                readonly: true,
+                                placeholder: "No bundle selected",
+                                value: import_file(),
+                            }
+                            button {
+                                class: "px-3 py-2 bg-gray-200 hover:bg-gray-300 rounded-md transition-colors",
+                                onclick: move |_| {
+                                    spawn(async move {
+                                        if let Some(path) = open_bundle_dialog().await {
+                                            let bundle_path = std::path::Path::new(&path);
+                                            import_bundle.set(Some(read_project_bundle(bundle_path, bundle_format_for(bundle_path))));
+                                            import_file.set(path);
+                                            import_result.set(None);
+                                        }
+                                    });
+                                },
+                                "Browse"
+                            }
+                        }
+                        div { class: "mt-4",
+                            label { class: "block text-sm font-medium text-gray-700 mb-1", "Path remapping" }
+                            textarea {
+                                class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                rows: 2,
+                                placeholder: "/Users/alice=/home/bob",
+                                value: import_remaps(),
+                                oninput: move |e| import_remaps.set(e.value()),
+                            }
+                        }
+                        div { class: "mt-4",
+                            label { class: "block text-sm font-medium text-gray-700 mb-1", "When a project already exists" }
+                            select {
+                                class: "w-full px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                onchange: move |e| import_policy.set(ConflictPolicy::from_name(&e.value()).unwrap_or_default()),
+                                option { value: "skip", selected: import_policy() == ConflictPolicy::Skip, "Skip it" }
+                                option { value: "overwrite", selected: import_policy() == ConflictPolicy::Overwrite, "Overwrite the existing project" }
+                                option { value: "rename", selected: import_policy() == ConflictPolicy::Rename, "Import it under a new name" }
+                            }
+                        }
+                        {
+                            let remaps: Result<Vec<_>, String> = import_remaps().lines()
+                                .filter(|line| !line.trim().is_empty())
+                                .map(parse_path_remap)
+                                .collect();
+                            rsx! {
+                                match (import_bundle(), remaps) {
+                                    (Some(Err(e)), _) | (Some(Ok(_)), Err(e)) => rsx! {
+                                        div { class: "mt-4 bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800", "{e}" }
+                                    },
+                                    (Some(Ok(incoming)), Ok(remaps)) => rsx! {
+                                        div { class: "mt-4 space-y-2",
+                                            for mut project in incoming.clone() {
+                                                {
+                                                    remap_project_paths(&mut project, &remaps);
+                                                    let exists = projects().iter().any(|p| p.id == project.id || p.name.to_lowercase() == project.name.to_lowercase());
+                                                    let missing = !std::path::Path::new(&project.path).exists();
+                                                    rsx! {
+                                                        div { class: "p-2 border border-gray-200 rounded",
+                                                            div { class: "flex items-center justify-between",
+                                                                span { class: "font-medium text-gray-900", "{project.name}" }
+                                                                if exists {
+                                                                    span { class: "text-xs bg-yellow-100 text-yellow-800 px-2 py-1 rounded", "Already exists" }
+                                                                }
+                                                            }
+                                                            div { class: "text-xs text-gray-500 truncate", "{project.path}" }
+                                                            if missing {
+                                                                div { class: "text-xs text-red-600", "⚠️ This folder does not exist here, add a path remapping" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        div { class: "flex justify-end mt-4",
+                                            button {
+                                                class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                                onclick: move |_| {
+                                                    import_result.set(Some(
+                                                        import_projects(incoming.clone(), import_policy(), &remaps).map(|outcomes| {
+                                                            let imported = outcomes.iter().filter(|o| o.action != ImportAction::Skipped).count();
+                                                            format!("✅ Imported {} of {} projects", imported, outcomes.len())
+                                                        }),
+                                                    ));
+                                                    projects.set(load_projects());
+                                                },
+                                                "Import {incoming.len()} Projects"
+                                            }
+                                        }
+                                    },
+                                    (None, _) => rsx! {},
+                                }
+                            }
+                        }
+                        if let Some(ref result) = import_result() {
+                            match result {
+                                Ok(msg) => rsx! { p { class: "text-sm text-green-600 mt-3", "{msg}" } },
+                                Err(msg) => rsx! { p { class: "text-sm text-red-600 mt-3", "{msg}" } },
+                            }
+                        }
+                        div { class: "flex justify-end mt-6",
+                            button {
+                                class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                onclick: move |_| close_import(),
+                                "Close"
+                            }
+                        }
+                    }
+                }
+            }
+
+            // New Profile Modal
+            if show_profile_modal() {
+                div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                    div { class: "bg-white rounded-lg p-6 w-full max-w-md mx-4",
+                        h2 { class: "text-xl font-semibold mb-1", "New Profile" }
+                        p { class: "text-sm text-gray-600 mb-4", "A profile has its own list of projects, e.g. \"work\" and \"oss\"." }
+                        input {
+                            class: "w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            r#type: "text",
+                            placeholder: "work",
+                            value: new_profile_name(),
+                            oninput: move |e| {
+                                new_profile_name.set(e.value());
+                                profile_error.set(None);
+                            },
+                        }
+                        if let Some(error) = profile_error() {
+                            p { class: "text-sm text-red-600 mt-2", "{error}" }
+                        }
+                        div { class: "flex justify-end space-x-3 mt-6",
+                            button {
+                                class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                onclick: move |_| {
+                                    show_profile_modal.set(false);
+                                    new_profile_name.set(String::new());
+                                    profile_error.set(None);
+                                },
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                disabled: new_profile_name().trim().is_empty(),
+                                onclick: move |_| {
+                                    let name = new_profile_name().trim().to_string();
+                                    match create_profile(&name) {
+                                        Ok(()) => {
+                                            profiles.set(list_profiles());
+                                            use_profile(name);
+                                            show_profile_modal.set(false);
+                                            new_profile_name.set(String::new());
+                                        }
+                                        Err(e) => profile_error.set(Some(e)),
+                                    }
+                                },
+                                "Create and Switch"
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Import Workspace Modal
+            if show_workspace_modal() {
+                div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                    div { class: "bg-white rounded-lg p-6 w-full max-w-2xl mx-4 max-h-[90vh] overflow-y-auto",
+                        h2 { class: "text-xl font-semibold mb-1", "Import Workspace" }
+                        p { class: "text-sm text-gray-600 mb-4",
+                            "Pick an npm, yarn or pnpm workspace root. Each package becomes a project linked to the workspace."
+                        }
+                        div { class: "flex space-x-2",
+                            input {
+                                class: "flex-1 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                r#type: "text",
+                                placeholder: "/path/to/monorepo",
+                                value: workspace_root(),
+                                oninput: move |e| workspace_root.set(e.value()),
+                            }
+                            button {
+                                class: "px-3 py-2 bg-gray-200 hover:bg-gray-300 rounded-md transition-colors",
+                                onclick: move |_| {
+                                    spawn(async move {
+                                        if let Some(path) = open_folder_dialog().await {
+                                            workspace_root.set(path);
+                                            scan_workspace();
+                                        }
+                                    });
+                                },
+                                "Browse"
+                            }
+                            button {
+                                class: "px-3 py-2 bg-blue-100 text-blue-800 hover:bg-blue-200 rounded-md transition-colors",
+                                disabled: workspace_root().trim().is_empty(),
+                                onclick: move |_| scan_workspace(),
+                                "Scan"
+                            }
+                        }
+                        match workspace_packages() {
+                            Some(Err(e)) => rsx! {
+                                div { class: "mt-4 bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800", "{e}" }
+                            },
+                            Some(Ok(packages)) if packages.is_empty() => rsx! {
+                                p { class: "mt-4 text-sm text-gray-600", "No packages match the workspace globs." }
+                            },
+                            Some(Ok(packages)) => rsx! {
+                                div { class: "mt-4 space-y-2",
+                                    for package in packages {
+                                        {
+                                            let already_imported = projects().iter().any(|p| p.path == package.path);
+                                            let path = package.path.clone();
+                                            rsx! {
+                                                label { class: "flex items-center space-x-3 p-2 border border-gray-200 rounded",
+                                                    input {
+                                                        r#type: "checkbox",
+                                                        disabled: already_imported,
+                                                        checked: selected_packages().contains(&package.path),
+                                                        onchange: move |_| {
+                                                            let mut selected = selected_packages();
+                                                            if let Some(index) = selected.iter().position(|p| p == &path) {
+                                                                selected.remove(index);
+                                                            } else {
+                                                                selected.push(path.clone());
+                                                            }
+                                                            selected_packages.set(selected);
+                                                        },
+                                                    }
+                                                    div { class: "flex-1 min-w-0",
+                                                        div { class: "font-medium text-gray-900",
+                                                            "{package.name}"
+                                                            span { class: "ml-2 text-xs text-gray-500", "v{package.version.clone().unwrap_or_else(|| \"?\".to_string())}" }
+                                                        }
+                                                        div { class: "text-xs text-gray-500 truncate", "{package.path}" }
+                                                    }
+                                                    if already_imported {
+                                                        span { class: "text-xs text-gray-500", "Already a project" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                div { class: "mt-4",
+                                    label { class: "block text-sm font-medium text-gray-700 mb-1", "Run scripts" }
+                                    select {
+                                        class: "w-full px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        onchange: move |e| workspace_scope.set(BuildScope::from_name(&e.value()).unwrap_or_default()),
+                                        option { value: "package", selected: workspace_scope() == BuildScope::Package, "In each package folder" }
+                                        option { value: "workspace", selected: workspace_scope() == BuildScope::Workspace, "From the workspace root, filtered to the package" }
+                                    }
+                                }
+                            },
+                            None => rsx! {},
+                        }
+                        div { class: "flex justify-end space-x-3 mt-6",
+                            button {
+                                class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                onclick: move |_| {
+                                    show_workspace_modal.set(false);
+                                    workspace_root.set(String::new());
+                                    workspace_packages.set(None);
+                                    selected_packages.set(Vec::new());
+                                },
+                                "Cancel"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                disabled: selected_packages().is_empty(),
+                                onclick: move |_| {
+                                    let root = workspace_root().trim().to_string();
+                                    let Some(Ok(packages)) = workspace_packages() else { return };
+                                    let imported: Vec<_> = packages.iter()
+                                        .filter(|p| selected_packages().contains(&p.path))
+                                        .map(|package| create_workspace_project(&root, package, workspace_scope()))
+                                        .collect();
+                                    if let Err(e) = update_projects(|stored| {
+                                        stored.extend(imported);
+                                        Ok(())
+                                    }) {
+                                        eprintln!("{}", e);
+                                    }
+                                    projects.set(load_projects());
+
+                                    show_workspace_modal.set(false);
+                                    workspace_root.set(String::new());
+                                    workspace_packages.set(None);
+                                    selected_packages.set(Vec::new());
+                                },
+                                "Import {selected_packages().len()} Packages"
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -1,7 +1,11 @@
+pub mod history;
 pub mod home;
 pub mod project_detail;
+pub mod recovery;
 pub mod settings;
 
+pub use history::ProjectHistory;
 pub use home::Home;
 pub use project_detail::ProjectDetail;
+pub use recovery::StoreRecovery;
 pub use settings::Settings;
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
-use crate::types::TargetPath;
+use crate::components::BuildLogView;
+use crate::types::{BuildLogLine, BuildPlan, BuildScope, BuildStatus, BuildStep, ConsumerCandidate, EnvFile, EnvVar, FileChangeKind, LogStream, OutputDir, PackageManager, Project, StepKind, TargetPath, VersionBump, WatchSettings};
 use crate::logic::*;
 
 /// Project Detail page
@@ -22,9 +23,118 @@ pub fn ProjectDetail(id: String) -> Element {
             let mut build_start_time = use_signal(|| None::<std::time::Instant>);
             let mut elapsed_time = use_signal(|| String::new());
             let mut build_process_handle = use_signal(|| None::<tokio::process::Child>);
+            let mut build_log = use_signal(Vec::<BuildLogLine>::new);
+            let mut build_preview = use_signal(|| None::<Result<BuildPlan, String>>);
+            let mut watch_task = use_signal(|| None::<dioxus::core::Task>);
+            let mut watch_status = use_signal(String::new);
+            let watch_settings_error = use_signal(|| None::<String>);
+            let mut consumer_search = use_signal(|| None::<Result<Vec<ConsumerCandidate>, String>>);
+            let mut is_searching_consumers = use_signal(|| false);
+            let mut is_rolling_back = use_signal(|| false);
+            let mut selected_consumers = use_signal(Vec::<String>::new); // Paths of the copies to add
+            let mut add_path_error = use_signal(|| None::<(String, bool)>); // Message, and whether forcing would get past it
+            let mut new_env_key = use_signal(String::new);
+            let mut new_env_value = use_signal(String::new);
+            let mut new_env_secret = use_signal(|| false);
+            let mut new_env_file = use_signal(String::new);
+            let mut env_error = use_signal(|| None::<String>);
+            let mut editing_step = use_signal(|| None::<Option<usize>>); // Some(None) adds a new step
+            let mut step_type = use_signal(|| "script".to_string());
+            let mut step_script = use_signal(String::new);
+            let mut step_line = use_signal(String::new); // Script arguments, or the program and its arguments
+            let mut step_shell = use_signal(String::new);
+            let mut step_timeout = use_signal(String::new);
+            let mut step_error = use_signal(|| None::<String>);
             
-            let commands = parse_package_json(&current_project().path);
+            let commands = current_project().build_commands;
+            // Selected scripts that were renamed or deleted in package.json
+            let stale_steps: Vec<String> = stale_build_steps(&current_project(), &commands).into_iter().map(String::from).collect();
             
+            // Keep the scripts in step with package.json, now and whenever it changes
+            use_future(move || async move {
+                refresh_build_commands(current_project);
+                let package_json = std::path::Path::new(&current_project.peek().path).join("package.json");
+                let mut watcher = match FileWatcher::new(&package_json) {
+                    Ok(watcher) => watcher,
+                    Err(e) => {
+                        eprintln!("package.json changes will not be picked up: {}", e);
+                        return;
+                    }
+                };
+                while watcher.next_change().await.is_some() {
+                    refresh_build_commands(current_project);
+                }
+            });
+            
+            // Reload when the store changes on disk, e.g. a target added from the CLI
+            let store_revision = use_context::<crate::StoreRevision>().0;
+            use_effect(move || {
+                store_revision();
+                let id = current_project.peek().id.clone();
+                if let Some(stored) = load_projects().into_iter().find(|p| p.id == id) {
+                    if stored != *current_project.peek() {
+                        current_project.set(stored);
+                    }
+                }
+            });
+            
+            // Add the typed path as a target once it passes the checks a build would run
+            let mut add_target = move |forced: bool| {
+                let path = new_path().trim().to_string();
+                if let Err(e) = check_target_access(&path).and_then(|_| check_target_location(&current_project(), &path)) {
+                    add_path_error.set(Some((e, false)));
+                    return;
+                }
+                if !forced {
+                    if let Err(e) = check_target_package(&current_project(), &path) {
+                        add_path_error.set(Some((e, true)));
+                        return;
+                    }
+                }
+
+                let target_path = TargetPath {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    path,
+                    is_active: true,
+                    version_bump: None,
+                    forced,
+                };
+
+                let mut proj = current_project();
+                proj.target_paths.push(target_path.clone());
+                current_project.set(proj.clone());
+
+                update_project(&proj.id, |p| p.target_paths.push(target_path));
+
+                show_add_path_modal.set(false);
+                new_path.set(String::new());
+                add_path_error.set(None);
+            };
+            // This project followed by the managed projects that consume it
+            let cascade_plan = plan_cascade(&load_projects(), &current_project().id);
+            // What "Auto-detect" resolves to, looking at the workspace root for linked packages
+            let detected_package_manager = resolve_package_manager(&Project { package_manager: None, ..current_project() });
+            
+            // Open the step editor on an existing step, or on a new one
+            let mut open_step_editor = move |index: Option<usize>| {
+                let step = index.and_then(|i| current_project().build_steps.get(i).cloned());
+                let (kind, script, line, shell) = match step.as_ref().map(|s| &s.kind) {
+                    Some(StepKind::Script { name, args }) => ("script", name.clone(), join_args(args), String::new()),
+                    Some(StepKind::Command { program, args }) => {
+                        ("command", String::new(), join_args(&[std::slice::from_ref(program), args].concat()), String::new())
+                    }
+                    Some(StepKind::Shell { script }) => ("shell", String::new(), String::new(), script.clone()),
+                    None => ("script", String::new(), String::new(), String::new()),
+                };
+                step_type.set(kind.to_string());
+                step_script.set(script);
+                step_line.set(line);
+                step_shell.set(shell);
+                step_timeout.set(step.and_then(|s| s.timeout_secs).map(|t| t.to_string()).unwrap_or_default());
+                step_error.set(None);
+                editing_step.set(Some(index));
+            };
+
             // Timer effect to update elapsed time during build
             use_future(move || {
                 async move {
@@ -47,6 +157,142 @@ pub fn ProjectDetail(id: String) -> Element {
                 }
             });
 
+            // Build the current project configuration; builds started by the watcher
+            // report in the watch status line instead of the result modal
+            let mut start_build = move |from_watch: bool| {
+                if is_building() { return; }
+                
+                let project = current_project();
+                is_building.set(true);
+                current_command.set("Starting build...".to_string());
+                build_log.set(Vec::new());
+                build_start_time.set(Some(std::time::Instant::now()));
+                elapsed_time.set("0s".to_string());
+                
+                spawn(async move {
+                    let result = build_and_update_project_cancellable(&project, current_command, build_log, build_process_handle).await;
+                    if from_watch {
+                        let time = chrono::Local::now().format("%H:%M:%S");
+                        match result {
+                            Ok(_) => watch_status.set(format!("✅ Rebuilt and updated targets at {}", time)),
+                            Err(e) => watch_status.set(format!("❌ Rebuild failed at {}: {}", time, e.lines().next().unwrap_or_default())),
+                        }
+                    } else {
+                        match result {
+                            Ok(_) => {
+                                result_message.set("✅ Build and update completed successfully!\n\nAll selected commands were executed and target paths were updated with the new version.".to_string());
+                                is_success.set(true);
+                            }
+                            Err(e) => {
+                                result_message.set(format!("Update failed: {}", e));
+                                is_success.set(false);
+                            }
+                        }
+                        show_result_modal.set(true);
+                    }
+                    is_building.set(false);
+                    current_command.set(String::new());
+                    build_start_time.set(None);
+                    elapsed_time.set(String::new());
+                    build_process_handle.set(None);
+                });
+            };
+            
+            // Build this project and then every downstream project in order, stopping
+            // at the first build that does not succeed cleanly
+            let mut start_cascade = move |order: Vec<Project>| {
+                if is_building() { return; }
+                
+                is_building.set(true);
+                current_command.set("Starting cascade...".to_string());
+                build_log.set(Vec::new());
+                build_start_time.set(Some(std::time::Instant::now()));
+                elapsed_time.set("0s".to_string());
+                
+                spawn(async move {
+                    let mut failure = None;
+                    for (index, project) in order.iter().enumerate() {
+                        build_log.write().push(BuildLogLine::new(
+                            LogStream::Info,
+                            format!("🔗 [{}/{}] Building {}", index + 1, order.len(), project.name),
+                        ));
+                        let mut reporter = SignalReporter {
+                            progress: current_command,
+                            log: build_log,
+                            process: build_process_handle,
+                        };
+                        let (result, record) = build_cascade_step(project, &mut reporter).await;
+                        match result {
+                            // Passed over, see cascade_skip_reason
+                            Ok(message) if record.is_none() => build_log.write().push(BuildLogLine::new(LogStream::Info, message)),
+                            Ok(_) if record.as_ref().is_some_and(|r| r.status == BuildStatus::Succeeded) => {}
+                            Ok(_) => failure = Some((index, "some targets could not be updated".to_string())),
+                            Err(e) => failure = Some((index, e)),
+                        }
+                        if failure.is_some() {
+                            break;
+                        }
+                    }
+                    
+                    let chain = order.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(" → ");
+                    match failure {
+                        None => {
+                            result_message.set(format!("✅ Cascade completed\n\n{}", chain));
+                            is_success.set(true);
+                        }
+                        Some((index, e)) => {
+                            let skipped = order[index + 1..].iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+                            let mut message = format!("Cascade stopped at {}: {}", order[index].name, e);
+                            if !skipped.is_empty() {
+                                message.push_str(&format!("\n\nNot built: {}", skipped.join(", ")));
+                            }
+                            result_message.set(message);
+                            is_success.set(false);
+                        }
+                    }
+                    is_building.set(false);
+                    current_command.set(String::new());
+                    build_start_time.set(None);
+                    elapsed_time.set(String::new());
+                    build_process_handle.set(None);
+                    show_result_modal.set(true);
+                });
+            };
+            
+            // Start or stop watching the project's sources
+            let mut toggle_watch = move || {
+                if let Some(task) = watch_task.take() {
+                    task.cancel();
+                    watch_status.set(String::new());
+                    return;
+                }
+                
+                match ProjectWatcher::new(&current_project()) {
+                    Ok(mut watcher) => {
+                        watch_status.set("👀 Watching for changes...".to_string());
+                        let task = spawn(async move {
+                            while let Some(changes) = watcher.next_changes().await {
+                                watch_status.set(format!("🔄 {} file(s) changed, rebuilding...", changes.len()));
+                                // Newer sources make the running build pointless
+                                if is_building() {
+                                    let _ = cancel_build_process(build_process_handle).await;
+                                    while is_building() {
+                                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                                    }
+                                }
+                                start_build(true);
+                            }
+                        });
+                        watch_task.set(Some(task));
+                    }
+                    Err(e) => {
+                        result_message.set(format!("Failed to start watch mode: {}", e));
+                        is_success.set(false);
+                        show_result_modal.set(true);
+                    }
+                }
+            };
+
             rsx! {
                 div { class: "min-h-screen bg-gray-50 p-6",
                     // Header
@@ -62,6 +308,58 @@ pub fn ProjectDetail(id: String) -> Element {
                                     "{current_project().name}"
                                 }
                                 p { class: "text-gray-600 mt-1", "{current_project().path}" }
+                                if let Some(link) = current_project().workspace {
+                                    p { class: "text-sm text-gray-500 mt-1",
+                                        "🧩 Workspace package {link.package_name} of {link.root}"
+                                    }
+                                }
+                                Link {
+                                    to: crate::Route::ProjectHistory { id: current_project().id.clone() },
+                                    class: "text-sm text-blue-600 hover:text-blue-800 mt-2 inline-block",
+                                    "📜 Build History"
+                                }
+                            }
+                            // Package manager selection (auto-detect or explicit override)
+                            div { class: "flex flex-col items-end",
+                                label { class: "text-sm font-medium text-gray-700 mb-1",
+                                    "Package Manager"
+                                }
+                                select {
+                                    class: "px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    value: current_project().package_manager.map(|pm| pm.to_string()).unwrap_or_else(|| "auto".to_string()),
+                                    onchange: move |e| {
+                                        let mut proj = current_project();
+                                        proj.package_manager = PackageManager::from_name(&e.value());
+                                        current_project.set(proj.clone());
+
+                                        update_project(&proj.id, |p| {
+                                            p.package_manager = proj.package_manager;
+                                        });
+                                    },
+                                    option { value: "auto",
+                                        "Auto-detect ({detected_package_manager})"
+                                    }
+                                    for pm in PackageManager::ALL {
+                                        option { value: "{pm}", "{pm}" }
+                                    }
+                                }
+                                // Where a workspace package's scripts run
+                                if let Some(link) = current_project().workspace {
+                                    label { class: "text-sm font-medium text-gray-700 mb-1 mt-3",
+                                        "Run Scripts From"
+                                    }
+                                    select {
+                                        class: "px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        value: "{link.scope}",
+                                        onchange: move |e| {
+                                            if let Some(scope) = BuildScope::from_name(&e.value()) {
+                                                persist_build_scope(current_project, scope);
+                                            }
+                                        },
+                                        option { value: "package", "Package folder" }
+                                        option { value: "workspace", "Workspace root" }
+                                    }
+                                }
                             }
                         }
                     }
@@ -77,9 +375,9 @@ pub fn ProjectDetail(id: String) -> Element {
                                     h2 { class: "text-xl font-semibold text-gray-900",
                                         "Build Commands"
                                     }
-                                    if !current_project().selected_build_commands.is_empty() {
+                                    if !current_project().build_steps.is_empty() {
                                         span { class: "bg-blue-100 text-blue-800 text-xs px-2 py-1 rounded-full",
-                                            "{current_project().selected_build_commands.len()} selected"
+                                            "{current_project().build_steps.len()} selected"
                                         }
                                     }
                                 }
@@ -105,7 +403,7 @@ pub fn ProjectDetail(id: String) -> Element {
                                                     div {
                                                         class: format!(
                                                             "p-3 border rounded-lg cursor-pointer transition-colors {}",
-                                                            if current_project().selected_build_commands.contains(cmd) {
+                                                            if has_script_step(&current_project(), cmd) {
                                                                 "border-green-500 bg-green-50"
                                                             } else {
                                                                 "border-gray-200 hover:border-gray-300"
@@ -114,21 +412,15 @@ pub fn ProjectDetail(id: String) -> Element {
                                                         onclick: {
                                                             let cmd = cmd.clone();
                                                             move |_| {
-                                                                let mut proj = current_project();
-                                                                if proj.selected_build_commands.contains(&cmd) {
+                                                                let mut steps = current_project().build_steps;
+                                                                if has_script_step(&current_project(), &cmd) {
                                                                     // Remove command
-                                                                    proj.selected_build_commands.retain(|c| c != &cmd);
+                                                                    steps.retain(|step| step.script_name() != Some(cmd.as_str()));
                                                                 } else {
                                                                     // Add command
-                                                                    proj.selected_build_commands.push(cmd.clone());
-                                                                }
-                                                                current_project.set(proj.clone());
-
-                                                                let mut all_projects = load_projects();
-                                                                if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                    p.selected_build_commands = proj.selected_build_commands.clone();
+                                                                    steps.push(BuildStep::script(&cmd));
                                                                 }
-                                                                save_projects(&all_projects);
+                                                                persist_build_steps(current_project, steps);
                                                             }
                                                         },
                                                         div { class: "flex items-center justify-between",
@@ -137,7 +429,7 @@ pub fn ProjectDetail(id: String) -> Element {
                                                                     "{cmd}"
                                                                 }
                                                             }
-                                                            if current_project().selected_build_commands.contains(cmd) {
+                                                            if has_script_step(&current_project(), cmd) {
                                                                 span { class: "text-green-600 font-bold",
                                                                     "✓"
                                                                 }
@@ -153,86 +445,112 @@ pub fn ProjectDetail(id: String) -> Element {
                                         }
                                     }
                                     // Selected Commands (with ordering)
-                                    if !current_project().selected_build_commands.is_empty() {
-                                        div { class: "border-t pt-4",
-                                            h3 { class: "text-lg font-medium text-gray-800 mb-3",
+                                    div { class: "border-t pt-4",
+                                        div { class: "flex items-center justify-between mb-3",
+                                            h3 { class: "text-lg font-medium text-gray-800",
                                                 "Execution Order"
                                             }
-                                            div { class: "space-y-2",
-                                                for (index , cmd) in current_project().selected_build_commands.iter().enumerate() {
-                                                    div { class: "flex items-center space-x-3 p-3 bg-blue-50 border border-blue-200 rounded-lg",
-                                                        // Order number
-                                                        div { class: "flex-shrink-0 w-8 h-8 bg-blue-600 text-white rounded-full flex items-center justify-center text-sm font-bold",
-                                                            "{index + 1}"
-                                                        }
-                                                        // Command name
-                                                        div { class: "flex-1",
-                                                            span { class: "font-medium text-gray-900",
-                                                                "{cmd}"
+                                            div { class: "flex items-center space-x-3",
+                                                label { class: "flex items-center space-x-2 text-sm text-gray-600",
+                                                    span { "Build timeout (s)" }
+                                                    input {
+                                                        class: "w-24 px-2 py-1 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                        r#type: "number",
+                                                        min: "0",
+                                                        placeholder: "None",
+                                                        value: current_project().build_timeout_secs.map(|s| s.to_string()).unwrap_or_default(),
+                                                        onchange: move |e| persist_build_timeout(current_project, parse_timeout(&e.value())),
+                                                    }
+                                                }
+                                                button {
+                                                    class: "bg-blue-600 hover:bg-blue-700 text-white px-3 py-1 rounded text-sm transition-colors",
+                                                    onclick: move |_| open_step_editor(None),
+                                                    "+ Add Step"
+                                                }
+                                            }
+                                        }
+                                        div { class: "space-y-2",
+                                            for (index , step) in current_project().build_steps.iter().enumerate() {
+                                                div {
+                                                    class: if step.script_name().is_some_and(|name| stale_steps.iter().any(|s| s == name)) {
+                                                        "flex items-center space-x-3 p-3 bg-red-50 border border-red-300 rounded-lg"
+                                                    } else {
+                                                        "flex items-center space-x-3 p-3 bg-blue-50 border border-blue-200 rounded-lg"
+                                                    },
+                                                    // Order number
+                                                    div { class: "flex-shrink-0 w-8 h-8 bg-blue-600 text-white rounded-full flex items-center justify-center text-sm font-bold",
+                                                        "{index + 1}"
+                                                    }
+                                                    // Step name and the command line it runs
+                                                    div { class: "flex-1 min-w-0",
+                                                        div { class: "flex items-center space-x-2",
+                                                            span { class: "font-medium text-gray-900 truncate",
+                                                                "{step.kind}"
                                                             }
-                                                        }
-                                                        // Move buttons
-                                                        div { class: "flex space-x-1",
-                                                            if index > 0 {
-                                                                button {
-                                                                    class: "p-1 text-gray-500 hover:text-gray-700 hover:bg-gray-200 rounded",
-                                                                    onclick: {
-                                                                        let index = index;
-                                                                        move |_| {
-                                                                            let mut proj = current_project();
-                                                                            proj.selected_build_commands.swap(index, index - 1);
-                                                                            current_project.set(proj.clone());
-
-                                                                            let mut all_projects = load_projects();
-                                                                            if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                                p.selected_build_commands = proj.selected_build_commands.clone();
-                                                                            }
-                                                                            save_projects(&all_projects);
-                                                                        }
-                                                                    },
-                                                                    "↑"
-                                                                }
+                                                            if step.script_name().is_none() {
+                                                                span { class: "text-xs bg-gray-200 text-gray-700 px-2 py-0.5 rounded", "{step.kind.type_name()}" }
                                                             }
-                                                            if index < current_project().selected_build_commands.len() - 1 {
-                                                                button {
-                                                                    class: "p-1 text-gray-500 hover:text-gray-700 hover:bg-gray-200 rounded",
-                                                                    onclick: {
-                                                                        let index = index;
-                                                                        move |_| {
-                                                                            let mut proj = current_project();
-                                                                            proj.selected_build_commands.swap(index, index + 1);
-                                                                            current_project.set(proj.clone());
-
-                                                                            let mut all_projects = load_projects();
-                                                                            if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                                p.selected_build_commands = proj.selected_build_commands.clone();
-                                                                            }
-                                                                            save_projects(&all_projects);
-                                                                        }
-                                                                    },
-                                                                    "↓"
-                                                                }
+                                                            if step.script_name().is_some_and(|name| stale_steps.iter().any(|s| s == name)) {
+                                                                span { class: "text-xs bg-red-100 text-red-800 px-2 py-0.5 rounded", "Not in package.json" }
+                                                            }
+                                                        }
+                                                        div { class: "text-xs font-mono text-gray-500 truncate",
+                                                            "{step_command_line(&current_project(), resolve_package_manager(&current_project()), step)}"
+                                                        }
+                                                    }
+                                                    // Kill the build when this step runs longer
+                                                    input {
+                                                        class: "w-24 px-2 py-1 text-sm border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                        r#type: "number",
+                                                        min: "0",
+                                                        title: "Timeout in seconds",
+                                                        placeholder: "Timeout (s)",
+                                                        value: step.timeout_secs.map(|s| s.to_string()).unwrap_or_default(),
+                                                        onchange: move |e| {
+                                                            let mut steps = current_project().build_steps;
+                                                            steps[index].timeout_secs = parse_timeout(&e.value());
+                                                            persist_build_steps(current_project, steps);
+                                                        },
+                                                    }
+                                                    // Move buttons
+                                                    div { class: "flex space-x-1",
+                                                        button {
+                                                            class: "p-1 text-gray-500 hover:text-gray-700 hover:bg-gray-200 rounded",
+                                                            onclick: move |_| open_step_editor(Some(index)),
+                                                            "✎"
+                                                        }
+                                                        if index > 0 {
+                                                            button {
+                                                                class: "p-1 text-gray-500 hover:text-gray-700 hover:bg-gray-200 rounded",
+                                                                onclick: move |_| {
+                                                                    let mut steps = current_project().build_steps;
+                                                                    steps.swap(index, index - 1);
+                                                                    persist_build_steps(current_project, steps);
+                                                                },
+                                                                "↑"
                                                             }
-                                                            // Remove button
+                                                        }
+                                                        if index < current_project().build_steps.len() - 1 {
                                                             button {
-                                                                class: "p-1 text-red-500 hover:text-red-700 hover:bg-red-100 rounded",
-                                                                onclick: {
-                                                                    let cmd = cmd.clone();
-                                                                    move |_| {
-                                                                        let mut proj = current_project();
-                                                                        proj.selected_build_commands.retain(|c| c != &cmd);
-                                                                        current_project.set(proj.clone());
-
-                                                                        let mut all_projects = load_projects();
-                                                                        if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                            p.selected_build_commands = proj.selected_build_commands.clone();
-                                                                        }
-                                                                        save_projects(&all_projects);
-                                                                    }
+                                                                class: "p-1 text-gray-500 hover:text-gray-700 hover:bg-gray-200 rounded",
+                                                                onclick: move |_| {
+                                                                    let mut steps = current_project().build_steps;
+                                                                    steps.swap(index, index + 1);
+                                                                    persist_build_steps(current_project, steps);
                                                                 },
-                                                                "✕"
+                                                                "↓"
                                                             }
                                                         }
+                                                        // Remove button
+                                                        button {
+                                                            class: "p-1 text-red-500 hover:text-red-700 hover:bg-red-100 rounded",
+                                                            onclick: move |_| {
+                                                                let mut steps = current_project().build_steps;
+                                                                steps.remove(index);
+                                                                persist_build_steps(current_project, steps);
+                                                            },
+                                                            "✕"
+                                                        }
                                                     }
                                                 }
                                             }
@@ -240,11 +558,19 @@ pub fn ProjectDetail(id: String) -> Element {
                                     }
                                 }
                             }
+                            // Selected scripts a build would fail on
+                            if !stale_steps.is_empty() {
+                                div { class: "mt-4 p-3 bg-red-50 border border-red-200 rounded-lg",
+                                    p { class: "text-sm text-red-800",
+                                        "⚠️ No longer in package.json: {stale_steps.join(\", \")}. Remove or edit these steps before building."
+                                    }
+                                }
+                            }
                             // Show message when accordion is closed but commands are selected
-                            if !show_commands_accordion() && !current_project().selected_build_commands.is_empty() {
+                            if !show_commands_accordion() && !current_project().build_steps.is_empty() {
                                 div { class: "mt-4 p-3 bg-blue-50 border border-blue-200 rounded-lg",
                                     p { class: "text-sm text-blue-800",
-                                        "Click to expand and manage your {current_project().selected_build_commands.len()} selected build commands"
+                                        "Click to expand and manage your {current_project().build_steps.len()} selected build commands"
                                     }
                                 }
                             }
@@ -264,10 +590,70 @@ pub fn ProjectDetail(id: String) -> Element {
                                 h2 { class: "text-xl font-semibold text-gray-900",
                                     "Target Paths"
                                 }
-                                button {
-                                    class: "bg-blue-600 hover:bg-blue-700 text-white px-3 py-1 rounded text-sm transition-colors",
-                                    onclick: move |_| show_add_path_modal.set(true),
-                                    "+ Add Path"
+                                div { class: "flex space-x-2",
+                                    button {
+                                        class: "bg-white border border-gray-300 hover:bg-gray-100 text-gray-800 px-3 py-1 rounded text-sm transition-colors",
+                                        disabled: is_searching_consumers(),
+                                        onclick: move |_| {
+                                            let project = current_project();
+                                            is_searching_consumers.set(true);
+                                            spawn(async move {
+                                                // Walking large folders would block the UI
+                                                let found = tokio::task::spawn_blocking(move || {
+                                                    find_consumers(&project, &consumer_search_roots(&project))
+                                                }).await.unwrap_or_else(|e| Err(format!("Search failed: {}", e)));
+                                                if let Ok(candidates) = &found {
+                                                    selected_consumers.set(candidates.iter()
+                                                        .filter(|c| !c.is_target)
+                                                        .map(|c| c.path.clone())
+                                                        .collect());
+                                                }
+                                                consumer_search.set(Some(found));
+                                                is_searching_consumers.set(false);
+                                            });
+                                        },
+                                        if is_searching_consumers() { "Searching..." } else { "🔍 Find Consumers" }
+                                    }
+                                    button {
+                                        class: "bg-blue-600 hover:bg-blue-700 text-white px-3 py-1 rounded text-sm transition-colors",
+                                        onclick: move |_| show_add_path_modal.set(true),
+                                        "+ Add Path"
+                                    }
+                                }
+                            }
+                            // Default version bump strategy for every target
+                            div { class: "mb-4 p-3 bg-gray-50 border border-gray-200 rounded-lg",
+                                label { class: "block text-sm font-medium text-gray-700 mb-1",
+                                    "Version Bump"
+                                }
+                                div { class: "flex space-x-2",
+                                    select {
+                                        class: "flex-1 px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        value: current_project().version_bump.kind(),
+                                        onchange: move |e| {
+                                            let identifier = current_project().version_bump.identifier().unwrap_or_default().to_string();
+                                            if let Some(strategy) = VersionBump::from_kind(&e.value(), &identifier) {
+                                                persist_version_bump(current_project, strategy);
+                                            }
+                                        },
+                                        for kind in VersionBump::KINDS {
+                                            option { value: kind, "{VersionBump::label_for_kind(kind)}" }
+                                        }
+                                    }
+                                    if let Some(identifier) = current_project().version_bump.identifier() {
+                                        input {
+                                            class: "w-28 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                            r#type: "text",
+                                            placeholder: VersionBump::DEFAULT_IDENTIFIER,
+                                            value: "{identifier}",
+                                            onchange: move |e| {
+                                                let kind = current_project().version_bump.kind();
+                                                if let Some(strategy) = VersionBump::from_kind(kind, &e.value()) {
+                                                    persist_version_bump(current_project, strategy);
+                                                }
+                                            },
+                                        }
+                                    }
                                 }
                             }
                             if current_project().target_paths.is_empty() {
@@ -294,11 +680,13 @@ pub fn ProjectDetail(id: String) -> Element {
                                                             proj.target_paths[index].is_active = e.checked();
                                                             current_project.set(proj.clone());
 
-                                                            let mut all_projects = load_projects();
-                                                            if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                p.target_paths[index].is_active = proj.target_paths[index].is_active;
-                                                            }
-                                                            save_projects(&all_projects);
+                                                            // Targets are matched by ID, the stored list may have changed meanwhile
+                                                            let target = &proj.target_paths[index];
+                                                            update_project(&proj.id, |p| {
+                                                                if let Some(stored) = p.target_paths.iter_mut().find(|t| t.id == target.id) {
+                                                                    stored.is_active = target.is_active;
+                                                                }
+                                                            });
                                                         }
                                                     },
                                                 }
@@ -310,6 +698,97 @@ pub fn ProjectDetail(id: String) -> Element {
                                                     div { class: "text-xs text-gray-500 mt-1",
                                                         "{target_path.path}"
                                                     }
+                                                    // Version this target will get on the next build
+                                                    match plan_target_version(&current_project(), target_path) {
+                                                        Ok((current_version, new_version)) => rsx! {
+                                                            div { class: "text-xs text-blue-700 mt-1",
+                                                                "v{current_version} → v{new_version}"
+                                                            }
+                                                        },
+                                                        Err(e) => rsx! {
+                                                            div { class: "text-xs text-red-600 mt-1", "{e}" }
+                                                        },
+                                                    }
+                                                    // A target inside the project is blocked even when forced
+                                                    if let Err(e) = check_target_location(&current_project(), &target_path.path) {
+                                                        div { class: "text-xs text-red-600 mt-1", "⚠️ {e}" }
+                                                    }
+                                                    // Package checks a build would block this target on, unless forced
+                                                    {
+                                                        let package_check = check_target_package(&current_project(), &target_path.path);
+                                                        let forced = target_path.forced;
+                                                        rsx! {
+                                                            if package_check.is_err() || forced {
+                                                                div { class: "flex items-center space-x-2 mt-1",
+                                                                    if let Err(e) = package_check {
+                                                                        span { class: if forced { "text-xs text-yellow-700" } else { "text-xs text-red-600" }, "⚠️ {e}" }
+                                                                    }
+                                                                    label { class: "flex items-center space-x-1 text-xs text-gray-700 flex-shrink-0",
+                                                                        input {
+                                                                            r#type: "checkbox",
+                                                                            checked: forced,
+                                                                            onchange: move |e| persist_target_forced(current_project, index, e.checked()),
+                                                                        }
+                                                                        span { "Forced" }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                                // Per-target version bump override
+                                                select {
+                                                    class: "px-2 py-1 text-xs border border-gray-300 rounded bg-white flex-shrink-0",
+                                                    value: target_path.version_bump.as_ref().map(|b| b.kind()).unwrap_or("project"),
+                                                    onchange: move |e| {
+                                                        let mut proj = current_project();
+                                                        // Prerelease overrides reuse the project's identifier when it has one
+                                                        let identifier = proj.version_bump.identifier().unwrap_or_default().to_string();
+                                                        proj.target_paths[index].version_bump = VersionBump::from_kind(&e.value(), &identifier);
+                                                        current_project.set(proj.clone());
+
+                                                        let target = &proj.target_paths[index];
+                                                        update_project(&proj.id, |p| {
+                                                            if let Some(stored) = p.target_paths.iter_mut().find(|t| t.id == target.id) {
+                                                                stored.version_bump = target.version_bump.clone();
+                                                            }
+                                                        });
+                                                    },
+                                                    option { value: "project", "Project default" }
+                                                    for kind in VersionBump::KINDS {
+                                                        option { value: kind, "{VersionBump::label_for_kind(kind)}" }
+                                                    }
+                                                }
+                                                // Undo the last update of this target
+                                                if let Some(backup) = latest_target_backup(&current_project().id, &target_path.id) {
+                                                    button {
+                                                        class: "px-3 py-1 text-xs bg-yellow-100 text-yellow-800 hover:bg-yellow-200 rounded transition-colors flex-shrink-0",
+                                                        disabled: is_building() || is_rolling_back(),
+                                                        title: "Restore the snapshot taken before the last update",
+                                                        onclick: move |_| {
+                                                            let backup = backup.clone();
+                                                            is_rolling_back.set(true);
+                                                            spawn(async move {
+                                                                // Copying large backups would block the UI
+                                                                let restored = tokio::task::spawn_blocking(move || rollback_target(&backup))
+                                                                    .await
+                                                                    .unwrap_or_else(|e| Err(format!("Rollback failed: {}", e)));
+                                                                match restored {
+                                                                    Ok(message) => {
+                                                                        result_message.set(message);
+                                                                        is_success.set(true);
+                                                                    }
+                                                                    Err(e) => {
+                                                                        result_message.set(format!("Rollback failed: {}", e));
+                                                                        is_success.set(false);
+                                                                    }
+                                                                }
+                                                                is_rolling_back.set(false);
+                                                                show_result_modal.set(true);
+                                                            });
+                                                        },
+                                                        if is_rolling_back() { "Restoring..." } else { "↺ Rollback" }
+                                                    }
                                                 }
                                                 // Remove button
                                                 button {
@@ -318,14 +797,10 @@ pub fn ProjectDetail(id: String) -> Element {
                                                         let index = index;
                                                         move |_| {
                                                             let mut proj = current_project();
-                                                            proj.target_paths.remove(index);
+                                                            let removed = proj.target_paths.remove(index);
                                                             current_project.set(proj.clone());
 
-                                                            let mut all_projects = load_projects();
-                                                            if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                                p.target_paths.remove(index);
-                                                            }
-                                                            save_projects(&all_projects);
+                                                            update_project(&proj.id, |p| p.target_paths.retain(|t| t.id != removed.id));
                                                         }
                                                     },
                                                     "Remove"
@@ -335,7 +810,7 @@ pub fn ProjectDetail(id: String) -> Element {
                                     }
                                 }
                                 if current_project().target_paths.iter().any(|p| p.is_active)
-                                    && !current_project().selected_build_commands.is_empty()
+                                    && !current_project().build_steps.is_empty()
                                 {
                                     div { class: "mt-6 pt-4 border-t",
                                         if is_building() {
@@ -399,41 +874,514 @@ pub fn ProjectDetail(id: String) -> Element {
                                             // Normal build button when not building
                                             button {
                                                 class: "w-full bg-blue-600 hover:bg-blue-700 text-white py-2 px-4 rounded-lg transition-colors",
-                                                onclick: {
-                                                    let project = current_project();
-                                                    move |_| {
-                                                        if is_building() { return; }
-                                                        
-                                                        let project_clone = project.clone();
-                                                        is_building.set(true);
-                                                        current_command.set("Starting build...".to_string());
-                                                        build_start_time.set(Some(std::time::Instant::now()));
-                                                        elapsed_time.set("0s".to_string());
-                                                        
-                                                        spawn(async move {
-                                                            match build_and_update_project_cancellable(&project_clone, current_command.clone(), build_process_handle.clone()).await {
-                                                                Ok(_) => {
-                                                                    result_message.set("✅ Build and update completed successfully!\n\nAll selected commands were executed and target paths were updated with the new version.".to_string());
-                                                                    is_success.set(true);
-                                                                }
-                                                                Err(e) => {
-                                                                    result_message.set(format!("Update failed: {}", e));
-                                                                    is_success.set(false);
-                                                                }
-                                                            }
-                                                            is_building.set(false);
-                                                            current_command.set(String::new());
-                                                            build_start_time.set(None);
-                                                            elapsed_time.set(String::new());
-                                                            build_process_handle.set(None);
-                                                            show_result_modal.set(true);
-                                                        });
-                                                    }
-                                                },
+                                                onclick: move |_| start_build(false),
                                                 "🚀 Build & Update Targets"
                                             }
+                                            button {
+                                                class: "w-full mt-2 bg-gray-200 hover:bg-gray-300 text-gray-800 py-2 px-4 rounded-lg transition-colors",
+                                                onclick: move |_| build_preview.set(Some(preview_build(&current_project()))),
+                                                "🔍 Preview"
+                                            }
+                                        }
+                                        button {
+                                            class: if watch_task().is_some() {
+                                                "w-full mt-2 bg-green-600 hover:bg-green-700 text-white py-2 px-4 rounded-lg transition-colors"
+                                            } else {
+                                                "w-full mt-2 bg-gray-200 hover:bg-gray-300 text-gray-800 py-2 px-4 rounded-lg transition-colors"
+                                            },
+                                            onclick: move |_| toggle_watch(),
+                                            if watch_task().is_some() { "⏹️ Stop Watching" } else { "👀 Watch & Rebuild" }
+                                        }
+                                        if !watch_status().is_empty() {
+                                            p { class: "text-sm text-gray-600 mt-2 text-center", "{watch_status()}" }
+                                        }
+                                        match cascade_plan.clone() {
+                                            Ok(order) if order.len() > 1 && !is_building() => rsx! {
+                                                button {
+                                                    class: "w-full mt-2 bg-indigo-600 hover:bg-indigo-700 text-white py-2 px-4 rounded-lg transition-colors",
+                                                    title: "Build this project, then every managed project that consumes it",
+                                                    onclick: {
+                                                        let order = order.clone();
+                                                        move |_| start_cascade(order.clone())
+                                                    },
+                                                    "🔗 Build & Cascade ({order.len()} projects)"
+                                                }
+                                                p { class: "text-xs text-gray-500 mt-1 text-center",
+                                                    {order.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join(" → ")}
+                                                }
+                                            },
+                                            Err(e) => rsx! {
+                                                p { class: "text-sm text-yellow-700 mt-2 text-center", "⚠️ {e}" }
+                                            },
+                                            _ => rsx! {},
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Build Log Section (live output of the current or last build)
+                    if !build_log().is_empty() {
+                        div { class: "max-w-4xl mx-auto mt-8 bg-white rounded-lg shadow-md p-6",
+                            div { class: "flex items-center justify-between mb-4",
+                                h2 { class: "text-xl font-semibold text-gray-900", "Build Log" }
+                                if !is_building() {
+                                    button {
+                                        class: "px-3 py-1 text-sm bg-gray-200 hover:bg-gray-300 rounded transition-colors",
+                                        onclick: move |_| build_log.set(Vec::new()),
+                                        "Clear"
+                                    }
+                                }
+                            }
+                            BuildLogView { lines: build_log() }
+                        }
+                    }
+
+                    // Build Output Section
+                    div { class: "max-w-4xl mx-auto mt-8 bg-white rounded-lg shadow-md p-6",
+                        div { class: "flex items-center justify-between mb-4",
+                            div {
+                                h2 { class: "text-xl font-semibold text-gray-900",
+                                    "Build Output"
+                                }
+                                p { class: "text-sm text-gray-500 mt-1",
+                                    "Folders copied from the project into each target after the build"
+                                }
+                            }
+                            div { class: "flex space-x-2",
+                                button {
+                                    class: "px-3 py-1 text-sm bg-gray-200 hover:bg-gray-300 rounded transition-colors",
+                                    onclick: move |_| {
+                                        let inferred = infer_output_dirs(&current_project().path);
+                                        persist_output_dirs(current_project, inferred);
+                                    },
+                                    "Reset from package.json"
+                                }
+                                button {
+                                    class: "bg-blue-600 hover:bg-blue-700 text-white px-3 py-1 rounded text-sm transition-colors",
+                                    onclick: move |_| {
+                                        let mut dirs = resolve_output_dirs(&current_project());
+                                        dirs.push(OutputDir { source: String::new(), destination: String::new() });
+                                        persist_output_dirs(current_project, dirs);
+                                    },
+                                    "+ Add Folder"
+                                }
+                            }
+                        }
+                        div { class: "space-y-2",
+                            div { class: "grid grid-cols-12 gap-2 text-xs font-medium text-gray-500 uppercase",
+                                div { class: "col-span-5", "Project folder" }
+                                div { class: "col-span-5", "Target subpath" }
+                            }
+                            for (index , dir) in resolve_output_dirs(&current_project()).iter().enumerate() {
+                                div { class: "grid grid-cols-12 gap-2 items-center",
+                                    input {
+                                        class: "col-span-5 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        r#type: "text",
+                                        placeholder: "dist",
+                                        value: "{dir.source}",
+                                        onchange: move |e| {
+                                            let mut dirs = resolve_output_dirs(&current_project());
+                                            let value = e.value().trim().to_string();
+                                            // Keep destination in step with source until it is set explicitly
+                                            if dirs[index].destination.is_empty() || dirs[index].destination == dirs[index].source {
+                                                dirs[index].destination = value.clone();
+                                            }
+                                            dirs[index].source = value;
+                                            persist_output_dirs(current_project, dirs);
+                                        },
+                                    }
+                                    input {
+                                        class: "col-span-5 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        r#type: "text",
+                                        placeholder: "dist",
+                                        value: "{dir.destination}",
+                                        onchange: move |e| {
+                                            let mut dirs = resolve_output_dirs(&current_project());
+                                            dirs[index].destination = e.value().trim().to_string();
+                                            persist_output_dirs(current_project, dirs);
+                                        },
+                                    }
+                                    button {
+                                        class: "col-span-2 px-3 py-2 text-xs bg-red-100 text-red-800 hover:bg-red-200 rounded transition-colors",
+                                        onclick: move |_| {
+                                            let mut dirs = resolve_output_dirs(&current_project());
+                                            dirs.remove(index);
+                                            persist_output_dirs(current_project, dirs);
+                                        },
+                                        "Remove"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Environment Section
+                    div { class: "max-w-4xl mx-auto mt-8 bg-white rounded-lg shadow-md p-6",
+                        div { class: "mb-4",
+                            h2 { class: "text-xl font-semibold text-gray-900", "Environment" }
+                            p { class: "text-sm text-gray-500 mt-1",
+                                "Variables passed to every build command. .env files are loaded first, in order; variables set here win. Secret values are masked in the build log and history."
+                            }
+                        }
+                        div { class: "space-y-2",
+                            for (index , file) in current_project().env_files.iter().enumerate() {
+                                {
+                                    let problem = std::fs::read_to_string(env_file_path(&current_project(), file))
+                                        .map_err(|e| e.to_string())
+                                        .and_then(|content| parse_env_file(&content))
+                                        .err();
+                                    rsx! {
+                                        div { class: "flex items-center space-x-3 p-2 border border-gray-200 rounded",
+                                            span { class: "text-sm", "📄" }
+                                            div { class: "flex-1 min-w-0",
+                                                div { class: "font-mono text-sm text-gray-900 truncate", "{file.path}" }
+                                                if let Some(problem) = problem {
+                                                    div { class: "text-xs text-red-600", "⚠️ {problem}" }
+                                                }
+                                            }
+                                            label { class: "flex items-center space-x-1 text-xs text-gray-600",
+                                                input {
+                                                    r#type: "checkbox",
+                                                    checked: file.secret,
+                                                    onchange: move |e| {
+                                                        let mut files = current_project().env_files;
+                                                        files[index].secret = e.checked();
+                                                        persist_env_files(current_project, files);
+                                                    },
+                                                }
+                                                span { "Secret" }
+                                            }
+                                            button {
+                                                class: "px-3 py-1 text-xs bg-red-100 text-red-800 hover:bg-red-200 rounded transition-colors",
+                                                onclick: move |_| {
+                                                    let mut files = current_project().env_files;
+                                                    files.remove(index);
+                                                    persist_env_files(current_project, files);
+                                                },
+                                                "Remove"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            div { class: "flex space-x-2",
+                                input {
+                                    class: "flex-1 px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    r#type: "text",
+                                    placeholder: ".env.production (relative to the project, or absolute)",
+                                    value: new_env_file(),
+                                    oninput: move |e| new_env_file.set(e.value()),
+                                }
+                                button {
+                                    class: "px-3 py-2 text-sm bg-gray-200 hover:bg-gray-300 rounded transition-colors",
+                                    disabled: new_env_file().trim().is_empty(),
+                                    onclick: move |_| {
+                                        let file = EnvFile { path: new_env_file().trim().to_string(), secret: false };
+                                        let mut files = current_project().env_files;
+                                        if files.iter().any(|f| f.path == file.path) {
+                                            env_error.set(Some(format!("{} is already loaded", file.path)));
+                                            return;
+                                        }
+                                        let path = env_file_path(&current_project(), &file);
+                                        let checked = std::fs::read_to_string(&path)
+                                            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+                                            .and_then(|content| parse_env_file(&content).map_err(|e| format!("Invalid env file {}: {}", path.display(), e)));
+                                        match checked {
+                                            Ok(_) => {
+                                                files.push(file);
+                                                persist_env_files(current_project, files);
+                                                new_env_file.set(String::new());
+                                                env_error.set(None);
+                                            }
+                                            Err(e) => env_error.set(Some(e)),
+                                        }
+                                    },
+                                    "+ Add .env File"
+                                }
+                            }
+                        }
+                        div { class: "space-y-2 mt-6",
+                            div { class: "grid grid-cols-12 gap-2 text-xs font-medium text-gray-500 uppercase",
+                                div { class: "col-span-4", "Name" }
+                                div { class: "col-span-5", "Value" }
+                            }
+                            for (index , var) in current_project().env.iter().enumerate() {
+                                div { class: "grid grid-cols-12 gap-2 items-center",
+                                    div { class: "col-span-4 px-3 py-2 font-mono text-sm text-gray-900 truncate", "{var.key}" }
+                                    input {
+                                        class: "col-span-5 px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                        r#type: if var.secret { "password" } else { "text" },
+                                        value: "{var.value}",
+                                        onchange: move |e| {
+                                            let mut vars = current_project().env;
+                                            vars[index].value = e.value();
+                                            persist_env(current_project, vars);
+                                        },
+                                    }
+                                    label { class: "col-span-1 flex items-center space-x-1 text-xs text-gray-600",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: var.secret,
+                                            onchange: move |e| {
+                                                let mut vars = current_project().env;
+                                                vars[index].secret = e.checked();
+                                                persist_env(current_project, vars);
+                                            },
+                                        }
+                                        span { "Secret" }
+                                    }
+                                    button {
+                                        class: "col-span-2 px-3 py-2 text-xs bg-red-100 text-red-800 hover:bg-red-200 rounded transition-colors",
+                                        onclick: move |_| {
+                                            let mut vars = current_project().env;
+                                            vars.remove(index);
+                                            persist_env(current_project, vars);
+                                        },
+                                        "Remove"
+                                    }
+                                }
+                            }
+                            div { class: "grid grid-cols-12 gap-2 items-center",
+                                input {
+                                    class: "col-span-4 px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    r#type: "text",
+                                    placeholder: "NODE_ENV",
+                                    value: new_env_key(),
+                                    oninput: move |e| new_env_key.set(e.value()),
+                                }
+                                input {
+                                    class: "col-span-5 px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    r#type: if new_env_secret() { "password" } else { "text" },
+                                    placeholder: "production",
+                                    value: new_env_value(),
+                                    oninput: move |e| new_env_value.set(e.value()),
+                                }
+                                label { class: "col-span-1 flex items-center space-x-1 text-xs text-gray-600",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: new_env_secret(),
+                                        onchange: move |e| new_env_secret.set(e.checked()),
+                                    }
+                                    span { "Secret" }
+                                }
+                                button {
+                                    class: "col-span-2 px-3 py-2 text-xs bg-blue-600 hover:bg-blue-700 text-white rounded transition-colors",
+                                    disabled: new_env_key().trim().is_empty(),
+                                    onclick: move |_| {
+                                        let key = new_env_key().trim().to_string();
+                                        if let Err(e) = validate_env_key(&key) {
+                                            env_error.set(Some(e));
+                                            return;
+                                        }
+                                        let var = EnvVar { key: key.clone(), value: new_env_value(), secret: new_env_secret() };
+                                        let mut vars = current_project().env;
+                                        match vars.iter_mut().find(|v| v.key == key) {
+                                            Some(existing) => *existing = var,
+                                            None => vars.push(var),
+                                        }
+                                        persist_env(current_project, vars);
+                                        new_env_key.set(String::new());
+                                        new_env_value.set(String::new());
+                                        new_env_secret.set(false);
+                                        env_error.set(None);
+                                    },
+                                    "+ Add"
+                                }
+                            }
+                        }
+                        if let Some(error) = env_error() {
+                            p { class: "text-sm text-red-600 mt-2", "{error}" }
+                        }
+                    }
+
+                    // Watch Mode Section
+                    div { class: "max-w-4xl mx-auto mt-8 bg-white rounded-lg shadow-md p-6",
+                        div { class: "mb-4",
+                            h2 { class: "text-xl font-semibold text-gray-900", "Watch Mode" }
+                            p { class: "text-sm text-gray-500 mt-1",
+                                "Files that trigger a rebuild, as globs relative to the project (one per line). Build output folders are always ignored."
+                            }
+                        }
+                        div { class: "grid grid-cols-2 gap-4",
+                            div {
+                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Include" }
+                                textarea {
+                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    rows: 4,
+                                    value: current_project().watch.include.join("\n"),
+                                    onchange: move |e| {
+                                        let mut settings = current_project().watch;
+                                        settings.include = parse_glob_lines(&e.value());
+                                        persist_watch_settings(current_project, watch_settings_error, settings);
+                                    },
+                                }
+                            }
+                            div {
+                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Exclude" }
+                                textarea {
+                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    rows: 4,
+                                    value: current_project().watch.exclude.join("\n"),
+                                    onchange: move |e| {
+                                        let mut settings = current_project().watch;
+                                        settings.exclude = parse_glob_lines(&e.value());
+                                        persist_watch_settings(current_project, watch_settings_error, settings);
+                                    },
+                                }
+                            }
+                        }
+                        div { class: "mt-4 flex items-center space-x-2",
+                            label { class: "text-sm font-medium text-gray-700", "Debounce (ms)" }
+                            input {
+                                class: "w-28 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                r#type: "number",
+                                min: "0",
+                                value: "{current_project().watch.debounce_ms}",
+                                onchange: move |e| {
+                                    if let Ok(debounce_ms) = e.value().trim().parse::<u64>() {
+                                        let mut settings = current_project().watch;
+                                        settings.debounce_ms = debounce_ms;
+                                        persist_watch_settings(current_project, watch_settings_error, settings);
+                                    }
+                                },
+                            }
+                        }
+                        if let Some(error) = watch_settings_error() {
+                            p { class: "text-sm text-red-600 mt-2", "{error}" }
+                        }
+                        if watch_task().is_some() {
+                            p { class: "text-xs text-gray-500 mt-2", "Changes apply the next time watch mode is started." }
+                        }
+                    }
+
+                    // Step Editor Modal
+                    if let Some(editing) = editing_step() {
+                        div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                            div { class: "bg-white rounded-lg p-6 w-full max-w-lg mx-4",
+                                h2 { class: "text-xl font-semibold mb-4",
+                                    if editing.is_some() { "Edit Build Step" } else { "Add Build Step" }
+                                }
+                                div { class: "space-y-4",
+                                    div {
+                                        label { class: "block text-sm font-medium text-gray-700 mb-1", "Type" }
+                                        select {
+                                            class: "w-full px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                            onchange: move |e| step_type.set(e.value()),
+                                            option { value: "script", selected: step_type() == "script", "package.json script" }
+                                            option { value: "command", selected: step_type() == "command", "Command" }
+                                            option { value: "shell", selected: step_type() == "shell", "Shell snippet" }
+                                        }
+                                    }
+                                    match step_type().as_str() {
+                                        "script" => rsx! {
+                                            div {
+                                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Script" }
+                                                select {
+                                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md bg-white focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                    onchange: move |e| step_script.set(e.value()),
+                                                    option { value: "", selected: step_script().is_empty(), "Choose a script" }
+                                                    for cmd in commands.iter() {
+                                                        option { value: "{cmd}", selected: step_script() == *cmd, "{cmd}" }
+                                                    }
+                                                }
+                                            }
+                                            div {
+                                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Arguments" }
+                                                input {
+                                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                    r#type: "text",
+                                                    placeholder: "--mode lib",
+                                                    value: step_line(),
+                                                    oninput: move |e| step_line.set(e.value()),
+                                                }
+                                            }
+                                        },
+                                        "command" => rsx! {
+                                            div {
+                                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Command line" }
+                                                input {
+                                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                    r#type: "text",
+                                                    placeholder: "npx tsc -p tsconfig.build.json",
+                                                    value: step_line(),
+                                                    oninput: move |e| step_line.set(e.value()),
+                                                }
+                                                p { class: "text-xs text-gray-500 mt-1",
+                                                    "Run directly in the project folder, without a shell. Quote arguments that contain spaces."
+                                                }
+                                            }
+                                        },
+                                        _ => rsx! {
+                                            div {
+                                                label { class: "block text-sm font-medium text-gray-700 mb-1", "Shell snippet" }
+                                                textarea {
+                                                    class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                                    rows: 3,
+                                                    placeholder: "./scripts/gen-icons.sh",
+                                                    value: step_shell(),
+                                                    oninput: move |e| step_shell.set(e.value()),
+                                                }
+                                                p { class: "text-xs text-gray-500 mt-1",
+                                                    "Run with sh -c (cmd /C on Windows) in the project folder."
+                                                }
+                                            }
+                                        },
+                                    }
+                                    div {
+                                        label { class: "block text-sm font-medium text-gray-700 mb-1", "Timeout (s)" }
+                                        input {
+                                            class: "w-32 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                            r#type: "number",
+                                            min: "0",
+                                            placeholder: "None",
+                                            value: step_timeout(),
+                                            oninput: move |e| step_timeout.set(e.value()),
                                         }
                                     }
+                                    if let Some(error) = step_error() {
+                                        p { class: "text-sm text-red-600", "{error}" }
+                                    }
+                                }
+                                div { class: "flex justify-end space-x-3 mt-6",
+                                    button {
+                                        class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                        onclick: move |_| editing_step.set(None),
+                                        "Cancel"
+                                    }
+                                    button {
+                                        class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                        onclick: move |_| {
+                                            let kind = match step_type().as_str() {
+                                                "script" if step_script().is_empty() => Err("Choose a script".to_string()),
+                                                "script" => split_args(&step_line()).map(|args| StepKind::Script { name: step_script(), args }),
+                                                "command" => split_args(&step_line()).and_then(|mut argv| {
+                                                    if argv.is_empty() {
+                                                        return Err("Enter the program to run".to_string());
+                                                    }
+                                                    let program = argv.remove(0);
+                                                    Ok(StepKind::Command { program, args: argv })
+                                                }),
+                                                _ if step_shell().trim().is_empty() => Err("Enter the shell snippet to run".to_string()),
+                                                _ => Ok(StepKind::Shell { script: step_shell().trim().to_string() }),
+                                            };
+                                            match kind {
+                                                Ok(kind) => {
+                                                    let step = BuildStep { kind, timeout_secs: parse_timeout(&step_timeout()) };
+                                                    let mut steps = current_project().build_steps;
+                                                    match editing {
+                                                        Some(index) if index < steps.len() => steps[index] = step,
+                                                        _ => steps.push(step),
+                                                    }
+                                                    persist_build_steps(current_project, steps);
+                                                    editing_step.set(None);
+                                                }
+                                                Err(e) => step_error.set(Some(e)),
+                                            }
+                                        },
+                                        "Save Step"
+                                    }
                                 }
                             }
                         }
@@ -455,7 +1403,10 @@ pub fn ProjectDetail(id: String) -> Element {
                                                 r#type: "text",
                                                 placeholder: "/path/to/target",
                                                 value: new_path(),
-                                                oninput: move |e| new_path.set(e.value()),
+                                                oninput: move |e| {
+                                                    new_path.set(e.value());
+                                                    add_path_error.set(None);
+                                                },
                                             }
                                             button {
                                                 class: "px-3 py-2 bg-gray-200 hover:bg-gray-300 rounded-md transition-colors",
@@ -463,6 +1414,7 @@ pub fn ProjectDetail(id: String) -> Element {
                                                     spawn(async move {
                                                         if let Some(path) = open_folder_dialog().await {
                                                             new_path.set(path);
+                                                            add_path_error.set(None);
                                                         }
                                                     });
                                                 },
@@ -471,39 +1423,225 @@ pub fn ProjectDetail(id: String) -> Element {
                                         }
                                     }
                                 }
+                                if let Some((error, can_force)) = add_path_error() {
+                                    div { class: "mt-4 bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800",
+                                        p { "{error}" }
+                                        if can_force {
+                                            p { class: "mt-1 text-red-700",
+                                                "Add it as a forced target to have builds update it anyway."
+                                            }
+                                        }
+                                    }
+                                }
                                 div { class: "flex justify-end space-x-3 mt-6",
                                     button {
                                         class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
                                         onclick: move |_| {
                                             show_add_path_modal.set(false);
                                             new_path.set(String::new());
+                                            add_path_error.set(None);
                                         },
                                         "Cancel"
                                     }
+                                    if matches!(add_path_error(), Some((_, true))) {
+                                        button {
+                                            class: "px-4 py-2 bg-yellow-500 hover:bg-yellow-600 text-white rounded-md transition-colors",
+                                            onclick: move |_| add_target(true),
+                                            "Add Anyway (Forced)"
+                                        }
+                                    }
                                     button {
                                         class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
                                         disabled: new_path().trim().is_empty(),
-                                        onclick: move |_| {
-                                            let target_path = TargetPath {
-                                                id: uuid::Uuid::new_v4().to_string(),
-                                                path: new_path().trim().to_string(),
-                                                is_active: true,
-                                            };
+                                        onclick: move |_| add_target(false),
+                                        "Add Path"
+                                    }
+                                }
+                            }
+                        }
+                    }
 
+                    // Find Consumers Modal
+                    if let Some(search) = consumer_search() {
+                        div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                            div { class: "bg-white rounded-lg p-6 w-full max-w-2xl mx-4 max-h-[90vh] overflow-y-auto",
+                                h2 { class: "text-xl font-semibold mb-1", "Consumers of {current_project().name}" }
+                                p { class: "text-sm text-gray-600 mb-4",
+                                    "Searched {consumer_search_roots(&current_project()).join(\", \")}. Search folders can be changed in Settings."
+                                }
+                                match search {
+                                    Err(e) => rsx! {
+                                        div { class: "bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800", "{e}" }
+                                    },
+                                    Ok(candidates) if candidates.is_empty() => rsx! {
+                                        p { class: "text-sm text-gray-600", "No installed copies of this package were found." }
+                                    },
+                                    Ok(candidates) => rsx! {
+                                        div { class: "space-y-2",
+                                            for candidate in candidates {
+                                                {
+                                                    let path = candidate.path.clone();
+                                                    rsx! {
+                                                        label { class: "flex items-center space-x-3 p-2 border border-gray-200 rounded",
+                                                            input {
+                                                                r#type: "checkbox",
+                                                                disabled: candidate.is_target,
+                                                                checked: candidate.is_target || selected_consumers().contains(&candidate.path),
+                                                                onchange: move |_| {
+                                                                    let mut selected = selected_consumers();
+                                                                    if let Some(index) = selected.iter().position(|p| p == &path) {
+                                                                        selected.remove(index);
+                                                                    } else {
+                                                                        selected.push(path.clone());
+                                                                    }
+                                                                    selected_consumers.set(selected);
+                                                                },
+                                                            }
+                                                            div { class: "flex-1 min-w-0",
+                                                                div { class: "font-medium text-gray-900 truncate", "{candidate.consumer_path}" }
+                                                                div { class: "text-xs text-gray-500 truncate", "{candidate.path}" }
+                                                            }
+                                                            span { class: "text-sm text-gray-700",
+                                                                "v{candidate.installed_version.clone().unwrap_or_else(|| \"?\".to_string())}"
+                                                            }
+                                                            if candidate.is_target {
+                                                                span { class: "text-xs text-gray-500", "Already a target" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    },
+                                }
+                                div { class: "flex justify-end space-x-3 mt-6",
+                                    button {
+                                        class: "px-4 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                        onclick: move |_| {
+                                            consumer_search.set(None);
+                                            selected_consumers.set(Vec::new());
+                                        },
+                                        "Close"
+                                    }
+                                    button {
+                                        class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                        disabled: selected_consumers().is_empty(),
+                                        onclick: move |_| {
+                                            let new_targets: Vec<TargetPath> = selected_consumers().into_iter()
+                                                .map(|path| TargetPath {
+                                                    id: uuid::Uuid::new_v4().to_string(),
+                                                    path,
+                                                    is_active: true,
+                                                    version_bump: None,
+                                                    forced: false,
+                                                })
+                                                .collect();
                                             let mut proj = current_project();
-                                            proj.target_paths.push(target_path);
+                                            proj.target_paths.extend(new_targets.clone());
                                             current_project.set(proj.clone());
 
-                                            let mut all_projects = load_projects();
-                                            if let Some(p) = all_projects.iter_mut().find(|p| p.id == proj.id) {
-                                                *p = proj;
-                                            }
-                                            save_projects(&all_projects);
+                                            update_project(&proj.id, |p| p.target_paths.extend(new_targets));
 
-                                            show_add_path_modal.set(false);
-                                            new_path.set(String::new());
+                                            consumer_search.set(None);
+                                            selected_consumers.set(Vec::new());
                                         },
-                                        "Add Path"
+                                        "Add {selected_consumers().len()} Targets"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Preview Modal (dry run, nothing is written)
+                    if let Some(preview) = build_preview() {
+                        div { class: "fixed inset-0 bg-black bg-opacity-50 flex items-center justify-center z-50",
+                            onclick: move |_| build_preview.set(None),
+                            div {
+                                class: "bg-white rounded-lg p-6 w-full max-w-3xl mx-4 max-h-[90vh] overflow-y-auto",
+                                onclick: move |e| e.stop_propagation(),
+                                h2 { class: "text-xl font-semibold mb-4", "Build Preview" }
+                                match preview {
+                                    Err(e) => rsx! {
+                                        div { class: "bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800", "{e}" }
+                                    },
+                                    Ok(plan) => rsx! {
+                                        div { class: "space-y-4",
+                                            div {
+                                                h3 { class: "font-medium text-gray-800 mb-1", "Commands ({plan.package_manager})" }
+                                                ol { class: "list-decimal list-inside text-sm font-mono text-gray-700",
+                                                    for (index , line) in plan.command_lines.iter().enumerate() {
+                                                        li {
+                                                            "{line}"
+                                                            if let Some(limit) = plan.command_timeouts.get(index).copied().flatten() {
+                                                                span { class: "text-gray-500", " (timeout {limit}s)" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                                if let Some(limit) = plan.build_timeout_secs {
+                                                    p { class: "text-xs text-gray-500 mt-1", "⏱️ Build timeout: {limit}s" }
+                                                }
+                                            }
+                                            if !plan.env.is_empty() {
+                                                div {
+                                                    h3 { class: "font-medium text-gray-800 mb-1", "Environment" }
+                                                    ul { class: "text-sm font-mono text-gray-700",
+                                                        for var in plan.env.iter() {
+                                                            li { "{var}" }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            for warning in plan.warnings.iter() {
+                                                div { class: "bg-yellow-50 border border-yellow-200 rounded-md p-2 text-sm text-yellow-800", "⚠️ {warning}" }
+                                            }
+                                            div {
+                                                h3 { class: "font-medium text-gray-800 mb-1", "Targets" }
+                                                div { class: "space-y-3",
+                                                    for target in plan.targets.iter() {
+                                                        div { class: "p-3 border border-gray-200 rounded-lg",
+                                                            div { class: "font-medium text-gray-900", "{extract_project_name(&target.path)}" }
+                                                            div { class: "text-xs text-gray-500", "{target.path}" }
+                                                            match (&target.current_version, &target.new_version) {
+                                                                (Some(current), Some(new)) => rsx! {
+                                                                    div { class: "text-xs text-blue-700 mt-1", "v{current} → v{new} ({target.version_bump})" }
+                                                                },
+                                                                _ => rsx! {},
+                                                            }
+                                                            if let Some(error) = &target.error {
+                                                                div { class: "text-xs text-red-600 mt-1", "{error}" }
+                                                            }
+                                                            if !target.file_changes.is_empty() {
+                                                                div { class: "mt-2 max-h-40 overflow-y-auto font-mono text-xs",
+                                                                    for change in target.file_changes.iter() {
+                                                                        div {
+                                                                            class: match change.kind {
+                                                                                FileChangeKind::Added => "text-green-700",
+                                                                                FileChangeKind::Changed => "text-yellow-700",
+                                                                                FileChangeKind::Removed => "text-red-700",
+                                                                            },
+                                                                            match change.kind {
+                                                                                FileChangeKind::Added => "+ ",
+                                                                                FileChangeKind::Changed => "~ ",
+                                                                                FileChangeKind::Removed => "- ",
+                                                                            }
+                                                                            "{change.path}"
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    },
+                                }
+                                div { class: "flex justify-end mt-4",
+                                    button {
+                                        class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                                        onclick: move |_| build_preview.set(None),
+                                        "Close"
                                     }
                                 }
                             }
@@ -560,3 +1698,136 @@ pub fn ProjectDetail(id: String) -> Element {
         }
     }
 }
+
+// Save the output directory list of the current project
+fn persist_output_dirs(mut current_project: Signal<Project>, dirs: Vec<OutputDir>) {
+    let mut proj = current_project();
+    proj.output_dirs = dirs;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.output_dirs = proj.output_dirs.clone();
+    });
+}
+
+// Save the default version bump strategy of the current project
+fn persist_version_bump(mut current_project: Signal<Project>, strategy: VersionBump) {
+    let mut proj = current_project();
+    proj.version_bump = strategy;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.version_bump = proj.version_bump.clone();
+    });
+}
+
+// Save whether a target is updated even when its package checks fail
+fn persist_target_forced(mut current_project: Signal<Project>, index: usize, forced: bool) {
+    let mut proj = current_project();
+    proj.target_paths[index].forced = forced;
+    current_project.set(proj.clone());
+
+    let target_id = &proj.target_paths[index].id;
+    update_project(&proj.id, |p| {
+        if let Some(stored) = p.target_paths.iter_mut().find(|t| &t.id == target_id) {
+            stored.forced = forced;
+        }
+    });
+}
+
+// Save the scope a workspace package's scripts run at
+fn persist_build_scope(mut current_project: Signal<Project>, scope: BuildScope) {
+    let mut proj = current_project();
+    let Some(link) = proj.workspace.as_mut() else { return };
+    link.scope = scope;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.workspace = proj.workspace.clone();
+    });
+}
+
+// Save the environment variables of the current project
+fn persist_env(mut current_project: Signal<Project>, vars: Vec<EnvVar>) {
+    let mut proj = current_project();
+    proj.env = vars;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.env = proj.env.clone();
+    });
+}
+
+// Save the .env files loaded for the current project
+fn persist_env_files(mut current_project: Signal<Project>, files: Vec<EnvFile>) {
+    let mut proj = current_project();
+    proj.env_files = files;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.env_files = proj.env_files.clone();
+    });
+}
+
+// Save the build steps of the current project
+fn persist_build_steps(mut current_project: Signal<Project>, steps: Vec<BuildStep>) {
+    let mut proj = current_project();
+    proj.build_steps = steps;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.build_steps = proj.build_steps.clone();
+    });
+}
+
+// Pick up scripts added, renamed or deleted in the project's package.json
+fn refresh_build_commands(mut current_project: Signal<Project>) {
+    let synced = sync_build_commands(&current_project.peek());
+    if let Some(synced) = synced {
+        current_project.set(synced);
+    }
+}
+
+fn has_script_step(project: &Project, script: &str) -> bool {
+    project.build_steps.iter().any(|step| step.script_name() == Some(script))
+}
+
+// Save how long a whole build of the current project may run
+fn persist_build_timeout(mut current_project: Signal<Project>, limit: Option<u64>) {
+    let mut proj = current_project();
+    proj.build_timeout_secs = limit;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.build_timeout_secs = proj.build_timeout_secs;
+    });
+}
+
+// Empty or 0 means no timeout
+fn parse_timeout(value: &str) -> Option<u64> {
+    value.trim().parse::<u64>().ok().filter(|seconds| *seconds > 0)
+}
+
+// Save the watch settings of the current project, unless a glob is invalid
+fn persist_watch_settings(mut current_project: Signal<Project>, mut error: Signal<Option<String>>, settings: WatchSettings) {
+    if let Err(e) = validate_watch_settings(&settings) {
+        error.set(Some(e));
+        return;
+    }
+    error.set(None);
+
+    let mut proj = current_project();
+    proj.watch = settings;
+    current_project.set(proj.clone());
+
+    update_project(&proj.id, |p| {
+        p.watch = proj.watch.clone();
+    });
+}
+
+fn parse_glob_lines(value: &str) -> Vec<String> {
+    value.lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
@@ -0,0 +1,76 @@
+use dioxus::prelude::*;
+use crate::logic::*;
+
+/// Store recovery page - shown instead of the app while projects.json cannot be read
+#[component]
+pub fn StoreRecovery(error: String, on_recovered: EventHandler<()>) -> Element {
+    let backups = use_signal(list_project_store_backups);
+    let mut action_error = use_signal(|| None::<String>);
+    let projects_file = get_projects_file().to_string_lossy().to_string();
+
+    rsx! {
+        div { class: "min-h-screen bg-gray-50 p-6",
+            div { class: "max-w-3xl mx-auto space-y-6",
+                // Header
+                div {
+                    h1 { class: "text-3xl font-bold text-gray-900", "⚠️ Project Configuration Unreadable" }
+                    p { class: "text-gray-600 mt-1", "{projects_file}" }
+                }
+
+                div { class: "bg-white rounded-lg shadow-md p-6 space-y-4",
+                    div { class: "bg-red-50 border border-red-200 rounded-md p-3 text-sm text-red-800 font-mono whitespace-pre-wrap",
+                        "{error}"
+                    }
+                    p { class: "text-sm text-gray-700",
+                        "Nothing is saved until this is resolved, so the file is left exactly as it is. Fix it by hand and retry, restore a backup, or start over with an empty project list."
+                    }
+                    if let Some(e) = action_error() {
+                        p { class: "text-sm text-red-600", "{e}" }
+                    }
+                    div { class: "flex space-x-3",
+                        button {
+                            class: "px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                            onclick: move |_| on_recovered.call(()),
+                            "Retry"
+                        }
+                        button {
+                            class: "px-4 py-2 bg-gray-200 hover:bg-gray-300 text-gray-800 rounded-md transition-colors",
+                            title: "The unreadable file is kept next to it",
+                            onclick: move |_| match set_aside_project_store() {
+                                Ok(_) => on_recovered.call(()),
+                                Err(e) => action_error.set(Some(e)),
+                            },
+                            "Start With No Projects"
+                        }
+                    }
+                }
+
+                // Backups
+                div { class: "bg-white rounded-lg shadow-md p-6",
+                    h2 { class: "text-xl font-semibold text-gray-900 mb-4", "Backups" }
+                    if backups().is_empty() {
+                        p { class: "text-sm text-gray-500", "No backups of the project list were found." }
+                    } else {
+                        div { class: "space-y-2",
+                            for backup in backups() {
+                                div { class: "flex items-center justify-between p-3 border border-gray-200 rounded-lg",
+                                    div { class: "text-sm text-gray-800 font-mono truncate mr-3",
+                                        "{backup.file_name().unwrap_or_default().to_string_lossy()}"
+                                    }
+                                    button {
+                                        class: "px-3 py-1 text-sm bg-yellow-100 text-yellow-800 hover:bg-yellow-200 rounded transition-colors flex-shrink-0",
+                                        onclick: move |_| match restore_project_store_backup(&backup) {
+                                            Ok(_) => on_recovered.call(()),
+                                            Err(e) => action_error.set(Some(e)),
+                                        },
+                                        "Restore"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use dioxus::prelude::*;
 use crate::Route;
+use crate::logic::{data_dir_source, get_data_dir, get_projects_file, load_settings, open_folder_dialog, save_settings, set_data_dir};
+use crate::types::DataDirSource;
 use std::process::Command;
 use std::env;
 use std::fs;
@@ -10,6 +12,27 @@ use std::path::Path;
 pub fn Settings() -> Element {
     let mut path_status = use_signal(|| check_path_status());
     let mut operation_result = use_signal(|| None::<Result<String, String>>);
+    let mut app_settings = use_signal(load_settings);
+    let mut settings_result = use_signal(|| None::<Result<String, String>>);
+    let mut consumer_roots_result = use_signal(|| None::<Result<String, String>>);
+    let mut store_file = use_context::<crate::StoreFile>().0;
+    let mut data_dir = use_signal(|| get_data_dir().to_string_lossy().to_string());
+    let mut new_data_dir = use_signal(String::new);
+    let mut data_dir_result = use_signal(|| None::<Result<String, String>>);
+
+    // Switch to another data folder and reload everything stored in it
+    let mut apply_data_dir = move |dir: Option<String>| {
+        match set_data_dir(dir.as_deref()) {
+            Ok(()) => {
+                data_dir.set(get_data_dir().to_string_lossy().to_string());
+                app_settings.set(load_settings());
+                store_file.set(get_projects_file());
+                new_data_dir.set(String::new());
+                data_dir_result.set(Some(Ok("✅ Data folder changed; data in the previous folder was left in place".to_string())));
+            }
+            Err(e) => data_dir_result.set(Some(Err(format!("❌ {}", e)))),
+        }
+    };
 
     rsx! {
         div { class: "min-h-screen bg-gray-50 p-6",
@@ -228,6 +251,122 @@ pub fn Settings() -> Element {
                         }
                     }
                 }
+                // Data folder
+                div { class: "bg-white rounded-lg shadow-sm border border-gray-200 p-6",
+                    h2 { class: "text-xl font-semibold text-gray-900 mb-4", "Data Folder" }
+                    p { class: "text-gray-600 text-sm mb-4",
+                        "Projects, profiles, build history and backups are stored here."
+                    }
+                    div { class: "text-sm font-mono bg-gray-50 border border-gray-200 rounded-md px-3 py-2 mb-4",
+                        "{data_dir}"
+                    }
+                    if matches!(data_dir_source(), DataDirSource::Flag | DataDirSource::Environment) {
+                        p { class: "text-sm text-yellow-700 mb-4",
+                            "Set by {data_dir_source()} for this run, which takes precedence over the folder chosen here."
+                        }
+                    }
+                    div { class: "flex space-x-2",
+                        input {
+                            class: "flex-1 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            r#type: "text",
+                            placeholder: "/path/to/data",
+                            value: new_data_dir(),
+                            oninput: move |e| new_data_dir.set(e.value()),
+                        }
+                        button {
+                            class: "px-3 py-2 bg-gray-200 hover:bg-gray-300 rounded-md transition-colors",
+                            onclick: move |_| {
+                                spawn(async move {
+                                    if let Some(path) = open_folder_dialog().await {
+                                        new_data_dir.set(path);
+                                    }
+                                });
+                            },
+                            "Browse"
+                        }
+                        button {
+                            class: "px-3 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-md transition-colors",
+                            disabled: new_data_dir().trim().is_empty(),
+                            onclick: move |_| apply_data_dir(Some(new_data_dir())),
+                            "Use Folder"
+                        }
+                        if data_dir_source() == DataDirSource::Settings {
+                            button {
+                                class: "px-3 py-2 text-gray-600 hover:text-gray-800 transition-colors",
+                                onclick: move |_| apply_data_dir(None),
+                                "Reset to Default"
+                            }
+                        }
+                    }
+                    if let Some(ref result) = data_dir_result() {
+                        match result {
+                            Ok(msg) => rsx! { p { class: "text-sm text-green-600 mt-3", "{msg}" } },
+                            Err(msg) => rsx! { p { class: "text-sm text-red-600 mt-3", "{msg}" } },
+                        }
+                    }
+                }
+                // Backups
+                div { class: "bg-white rounded-lg shadow-sm border border-gray-200 p-6",
+                    h2 { class: "text-xl font-semibold text-gray-900 mb-4", "Backups" }
+                    p { class: "text-gray-600 text-sm mb-4",
+                        "Before a build updates a target, its build output folders and package.json are saved so they can be rolled back from the build history or with 'library-build-management rollback'."
+                    }
+                    div { class: "flex items-center space-x-3",
+                        label { class: "text-sm font-medium text-gray-700", "Keep snapshots of the last" }
+                        input {
+                            class: "w-20 px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            r#type: "number",
+                            min: "1",
+                            value: "{app_settings().backup_retention}",
+                            onchange: move |e| {
+                                match e.value().trim().parse::<usize>() {
+                                    Ok(retention) if retention > 0 => {
+                                        let mut settings = app_settings();
+                                        settings.backup_retention = retention;
+                                        settings_result.set(Some(save_settings(&settings).map(|_| "✅ Settings saved".to_string())));
+                                        app_settings.set(settings);
+                                    }
+                                    _ => settings_result.set(Some(Err("❌ Enter a number of builds greater than 0".to_string()))),
+                                }
+                            },
+                        }
+                        span { class: "text-sm text-gray-700", "builds per project" }
+                    }
+                    if let Some(ref result) = settings_result() {
+                        match result {
+                            Ok(msg) => rsx! { p { class: "text-sm text-green-600 mt-3", "{msg}" } },
+                            Err(msg) => rsx! { p { class: "text-sm text-red-600 mt-3", "{msg}" } },
+                        }
+                    }
+                }
+                // Consumer discovery
+                div { class: "bg-white rounded-lg shadow-sm border border-gray-200 p-6",
+                    h2 { class: "text-xl font-semibold text-gray-900 mb-4", "Find Consumers" }
+                    p { class: "text-gray-600 text-sm mb-4",
+                        "Folders searched for node_modules that contain a project's package, one per line. When empty, the folder containing the project is searched."
+                    }
+                    textarea {
+                        class: "w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                        rows: 3,
+                        placeholder: "/home/me/work",
+                        value: app_settings().consumer_search_roots.join("\n"),
+                        onchange: move |e| {
+                            let mut settings = app_settings();
+                            settings.consumer_search_roots = e.value().lines()
+                                .map(|line| line.trim().to_string())
+                                .filter(|line| !line.is_empty())
+                                .collect();
+                            consumer_roots_result.set(Some(save_settings(&settings).map(|_| "✅ Settings saved".to_string())));
+                            app_settings.set(settings);
+                        },
+                    }
+                    if let Some(ref result) = consumer_roots_result() {
+                        match result {
+                            Ok(msg) => rsx! { p { class: "text-sm text-green-600 mt-3", "{msg}" } },
+                            Err(msg) => rsx! { p { class: "text-sm text-red-600 mt-3", "{msg}" } },
+                        }
+                    }
+                }
                 // CLI Usage Examples
                 div { class: "bg-white rounded-lg shadow-sm border border-gray-200 p-6",
                     h2 { class: "text-xl font-semibold text-gray-900 mb-4", "CLI Usage Examples" }
@@ -236,11 +375,86 @@ pub fn Settings() -> Element {
                             div { "# List all projects" }
                             div { class: "text-white", "$ library-build-management list" }
                             br {}
+                            div { "# Set up a project without the GUI" }
+                            div { class: "text-white",
+                                "$ library-build-management project add --name \"My Project\" --path ./my-lib"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management commands select --project \"My Project\" build:types build"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management commands timeout --project \"My Project\" --step build 300"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management commands add --project \"My Project\" --program npx -- tsc -p tsconfig.build.json"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management project set \"My Project\" --package-manager pnpm --output-dir dist --version-bump prerelease"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management target add --project \"My Project\" ../app/node_modules/my-lib"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management project import-workspace --path ./monorepo --scope workspace"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management target find --project \"My Project\" --root ~/work --add --path ~/work/my-app"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management env set --project \"My Project\" NPM_TOKEN=... --secret"
+                            }
+                            br {}
+                            div { "# Use another profile or data folder for one run" }
+                            div { class: "text-white",
+                                "$ library-build-management --profile oss list"
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management --config-dir /tmp/lbm-test list"
+                            }
+                            br {}
+                            div { "# Share project definitions with a teammate" }
+                            div { class: "text-white",
+                                "$ library-build-management export team.toml --project \"My Project\""
+                            }
+                            div { class: "text-white",
+                                "$ library-build-management import team.toml --on-conflict rename --remap /Users/alice=/home/bob"
+                            }
+                            br {}
                             div { "# Build a specific project" }
                             div { class: "text-white",
                                 "$ library-build-management build --project \"My Project\""
                             }
                             br {}
+                            div { "# Preview a build without running or writing anything" }
+                            div { class: "text-white",
+                                "$ library-build-management build --project \"My Project\" --dry-run"
+                            }
+                            br {}
+                            div { "# Rebuild and update targets whenever the sources change" }
+                            div { class: "text-white",
+                                "$ library-build-management build --project \"My Project\" --watch"
+                            }
+                            br {}
+                            div { "# Build a library and every managed project that consumes it" }
+                            div { class: "text-white",
+                                "$ library-build-management build --project \"My Project\" --cascade"
+                            }
+                            br {}
+                            div { "# Show the build history of a project" }
+                            div { class: "text-white",
+                                "$ library-build-management history --project \"My Project\""
+                            }
+                            br {}
+                            div { "# Roll back the targets updated by the last build" }
+                            div { class: "text-white",
+                                "$ library-build-management rollback --project \"My Project\""
+                            }
+                            br {}
+                            div { "# Machine-readable output for scripts (works with every command)" }
+                            div { class: "text-white",
+                                "$ library-build-management build --project \"My Project\" --output json"
+                            }
+                            br {}
                             div { "# Show help" }
                             div { class: "text-white", "$ library-build-management --help" }
                         }
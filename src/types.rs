@@ -5,9 +5,158 @@ pub struct Project {
     pub id: String,
     pub name: String,
     pub path: String,
-    pub build_commands: Vec<String>,
-    pub selected_build_commands: Vec<String>, // Ordered list of selected commands
+    pub build_commands: Vec<String>, // Scripts found in package.json
+    pub build_steps: Vec<BuildStep>, // Run in order by every build
     pub target_paths: Vec<TargetPath>,
+    #[serde(default)]
+    pub package_manager: Option<PackageManager>, // None = auto-detect from lockfiles
+    #[serde(default)]
+    pub output_dirs: Vec<OutputDir>, // Empty = infer from package.json
+    #[serde(default)]
+    pub version_bump: VersionBump,
+    #[serde(default)]
+    pub watch: WatchSettings,
+    #[serde(default)]
+    pub workspace: Option<WorkspaceLink>, // Set for packages imported from a monorepo
+    #[serde(default)]
+    pub env: Vec<EnvVar>, // Set on every build command, after env_files
+    #[serde(default)]
+    pub env_files: Vec<EnvFile>, // Loaded in order, later files win
+    #[serde(default)]
+    pub build_timeout_secs: Option<u64>, // Limit for all steps together
+}
+
+/// One command a build runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildStep {
+    pub kind: StepKind,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+impl BuildStep {
+    pub fn script(name: &str) -> Self {
+        BuildStep {
+            kind: StepKind::Script { name: name.to_string(), args: Vec::new() },
+            timeout_secs: None,
+        }
+    }
+
+    /// Name of the package.json script this step runs, if it runs one
+    pub fn script_name(&self) -> Option<&str> {
+        match &self.kind {
+            StepKind::Script { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StepKind {
+    /// A package.json script run by the package manager, with extra arguments for it
+    Script {
+        name: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// A program run directly, e.g. `npx` with `["tsc", "-p", "tsconfig.build.json"]`
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// A snippet for the system shell (`sh -c` or `cmd /C`)
+    Shell { script: String },
+}
+
+impl StepKind {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            StepKind::Script { .. } => "script",
+            StepKind::Command { .. } => "command",
+            StepKind::Shell { .. } => "shell",
+        }
+    }
+}
+
+/// Short name of a step for progress and history, e.g. "build:types" or "npx tsc"
+impl std::fmt::Display for StepKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepKind::Script { name, args } if args.is_empty() => write!(f, "{}", name),
+            StepKind::Script { name, args } => write!(f, "{} {}", name, args.join(" ")),
+            StepKind::Command { program, args } if args.is_empty() => write!(f, "{}", program),
+            StepKind::Command { program, args } => write!(f, "{} {}", program, args.join(" ")),
+            StepKind::Shell { script } => write!(f, "{}", script.lines().next().unwrap_or_default().trim()),
+        }
+    }
+}
+
+/// An environment variable passed to the build commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub secret: bool, // Masked in the build log and history
+}
+
+/// A .env file whose variables are passed to the build commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvFile {
+    pub path: String, // Relative to the project root, or absolute
+    #[serde(default)]
+    pub secret: bool, // Mask every value the file sets
+}
+
+/// Link from a workspace package to the monorepo it was imported from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceLink {
+    pub root: String,         // Workspace root folder
+    pub package_name: String, // `name` from the package's package.json
+    #[serde(default)]
+    pub scope: BuildScope,
+}
+
+/// Where a workspace package's scripts are run from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildScope {
+    /// In the package folder, like a standalone project
+    #[default]
+    Package,
+    /// From the workspace root, filtered to the package (e.g. `pnpm --filter`)
+    Workspace,
+}
+
+impl BuildScope {
+    pub const ALL: [BuildScope; 2] = [BuildScope::Package, BuildScope::Workspace];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildScope::Package => "package",
+            BuildScope::Workspace => "workspace",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BuildScope> {
+        BuildScope::ALL.iter().copied().find(|s| s.as_str() == name.trim().to_lowercase())
+    }
+}
+
+impl std::fmt::Display for BuildScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A package found below a workspace root, not imported yet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspacePackage {
+    pub name: String,
+    pub path: String,
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -15,4 +164,569 @@ pub struct TargetPath {
     pub id: String,
     pub path: String,
     pub is_active: bool,
+    #[serde(default)]
+    pub version_bump: Option<VersionBump>, // None = use the project's strategy
+    #[serde(default)]
+    pub forced: bool, // Update even when the target's package name does not match
+}
+
+/// A build output folder and where it lands inside each target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputDir {
+    pub source: String,      // Relative to the project root, e.g. "lib" or "esm"
+    pub destination: String, // Relative to the target root, usually the same as source
+}
+
+/// Which source changes trigger a rebuild in watch mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchSettings {
+    pub include: Vec<String>, // Globs relative to the project root, e.g. "src/**"
+    pub exclude: Vec<String>, // Output folders are always excluded as well
+    pub debounce_ms: u64,     // Quiet period before a batch of changes triggers a build
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        Self {
+            include: vec!["src/**".to_string()],
+            exclude: vec!["**/node_modules/**".to_string(), "**/.git/**".to_string()],
+            debounce_ms: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Npm,
+    Pnpm,
+    Yarn,
+    Bun,
+}
+
+impl PackageManager {
+    pub const ALL: [PackageManager; 4] = [
+        PackageManager::Npm,
+        PackageManager::Pnpm,
+        PackageManager::Yarn,
+        PackageManager::Bun,
+    ];
+
+    /// Executable name used for PATH lookups
+    pub fn binary_name(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Lockfile that identifies a project managed by this tool
+    pub fn lockfile(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "package-lock.json",
+            PackageManager::Pnpm => "pnpm-lock.yaml",
+            PackageManager::Yarn => "yarn.lock",
+            PackageManager::Bun => "bun.lockb",
+        }
+    }
+
+    /// Extra arguments passed on to a script; npm needs them after `--`
+    pub fn script_extra_args(&self, args: &[String]) -> Vec<String> {
+        match self {
+            PackageManager::Npm if !args.is_empty() => std::iter::once("--".to_string()).chain(args.iter().cloned()).collect(),
+            _ => args.to_vec(),
+        }
+    }
+
+    /// Arguments needed to run a package.json script
+    /// yarn runs scripts directly (`yarn build`), the others need `run`
+    pub fn run_args(&self, script: &str) -> Vec<String> {
+        match self {
+            PackageManager::Yarn => vec![script.to_string()],
+            _ => vec!["run".to_string(), script.to_string()],
+        }
+    }
+
+    /// Arguments needed to run one workspace package's script from the workspace root
+    pub fn workspace_run_args(&self, package: &str, script: &str) -> Vec<String> {
+        let (package, script) = (package.to_string(), script.to_string());
+        match self {
+            PackageManager::Npm => vec!["run".to_string(), script, format!("--workspace={}", package)],
+            PackageManager::Pnpm => vec!["--filter".to_string(), package, "run".to_string(), script],
+            PackageManager::Yarn => vec!["workspace".to_string(), package, "run".to_string(), script],
+            PackageManager::Bun => vec!["run".to_string(), "--filter".to_string(), package, script],
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PackageManager> {
+        PackageManager::ALL
+            .iter()
+            .copied()
+            .find(|pm| pm.binary_name() == name.trim().to_lowercase())
+    }
+}
+
+impl std::fmt::Display for PackageManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.binary_name())
+    }
+}
+
+/// How the version written into a target's package.json is computed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VersionBump {
+    #[default]
+    Patch,
+    Minor,
+    Major,
+    /// `1.4.2` -> `1.4.3-local.0` -> `1.4.3-local.1`
+    Prerelease { identifier: String },
+    /// `1.4.2` -> `1.4.3-local.20261017153000`
+    TimestampPrerelease { identifier: String },
+    /// Use the version from the source project's package.json
+    KeepSource,
+    /// Leave the target's current version as it is
+    Unchanged,
+}
+
+impl VersionBump {
+    pub const KINDS: [&'static str; 7] = [
+        "patch",
+        "minor",
+        "major",
+        "prerelease",
+        "timestamp_prerelease",
+        "keep_source",
+        "unchanged",
+    ];
+
+    pub const DEFAULT_IDENTIFIER: &'static str = "local";
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            VersionBump::Patch => "patch",
+            VersionBump::Minor => "minor",
+            VersionBump::Major => "major",
+            VersionBump::Prerelease { .. } => "prerelease",
+            VersionBump::TimestampPrerelease { .. } => "timestamp_prerelease",
+            VersionBump::KeepSource => "keep_source",
+            VersionBump::Unchanged => "unchanged",
+        }
+    }
+
+    /// Build a strategy from its kind, using `identifier` for the prerelease variants
+    pub fn from_kind(kind: &str, identifier: &str) -> Option<VersionBump> {
+        let identifier = if identifier.trim().is_empty() {
+            VersionBump::DEFAULT_IDENTIFIER.to_string()
+        } else {
+            identifier.trim().to_string()
+        };
+        match kind {
+            "patch" => Some(VersionBump::Patch),
+            "minor" => Some(VersionBump::Minor),
+            "major" => Some(VersionBump::Major),
+            "prerelease" => Some(VersionBump::Prerelease { identifier }),
+            "timestamp_prerelease" => Some(VersionBump::TimestampPrerelease { identifier }),
+            "keep_source" => Some(VersionBump::KeepSource),
+            "unchanged" => Some(VersionBump::Unchanged),
+            _ => None,
+        }
+    }
+
+    pub fn identifier(&self) -> Option<&str> {
+        match self {
+            VersionBump::Prerelease { identifier } | VersionBump::TimestampPrerelease { identifier } => Some(identifier),
+            _ => None,
+        }
+    }
+
+    pub fn label_for_kind(kind: &str) -> &'static str {
+        match kind {
+            "patch" => "Patch (1.4.2 → 1.4.3)",
+            "minor" => "Minor (1.4.2 → 1.5.0)",
+            "major" => "Major (1.4.2 → 2.0.0)",
+            "prerelease" => "Prerelease (1.4.3-id.N)",
+            "timestamp_prerelease" => "Timestamped prerelease",
+            "keep_source" => "Keep source version",
+            "unchanged" => "Leave target version unchanged",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for VersionBump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionBump::Prerelease { identifier } => write!(f, "prerelease ({}.N)", identifier),
+            VersionBump::TimestampPrerelease { identifier } => write!(f, "timestamped prerelease ({}.<timestamp>)", identifier),
+            other => write!(f, "{}", other.kind().replace('_', " ")),
+        }
+    }
+}
+
+/// Where a build log line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+    Info, // Progress messages from the app itself
+}
+
+/// A single line of build output, as shown in the log panel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildLogLine {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub stream: LogStream,
+    pub text: String,
+    #[serde(default)]
+    pub step: Option<usize>, // Index of the build command that printed it
+}
+
+impl BuildLogLine {
+    pub fn new(stream: LogStream, text: impl Into<String>) -> Self {
+        BuildLogLine {
+            timestamp: chrono::Local::now(),
+            stream,
+            text: text.into(),
+            step: None,
+        }
+    }
+}
+
+/// Final state of a recorded build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildStatus {
+    Succeeded,
+    PartiallyFailed, // Build passed but at least one target could not be updated
+    Failed,
+    Cancelled,
+    TimedOut, // A command or the whole build ran past its timeout and was killed
+}
+
+impl BuildStatus {
+    pub const ALL: [BuildStatus; 5] = [
+        BuildStatus::Succeeded,
+        BuildStatus::PartiallyFailed,
+        BuildStatus::Failed,
+        BuildStatus::Cancelled,
+        BuildStatus::TimedOut,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildStatus::Succeeded => "succeeded",
+            BuildStatus::PartiallyFailed => "partially_failed",
+            BuildStatus::Failed => "failed",
+            BuildStatus::Cancelled => "cancelled",
+            BuildStatus::TimedOut => "timed_out",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<BuildStatus> {
+        BuildStatus::ALL.iter().copied().find(|s| s.as_str() == name)
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            BuildStatus::Succeeded => "✅",
+            BuildStatus::PartiallyFailed => "⚠️",
+            BuildStatus::Failed => "❌",
+            BuildStatus::Cancelled => "⏹️",
+            BuildStatus::TimedOut => "⏱️",
+        }
+    }
+}
+
+impl std::fmt::Display for BuildStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str().replace('_', " "))
+    }
+}
+
+/// What happened to one target during a build
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetOutcome {
+    pub target_id: String,
+    pub path: String,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+    pub error: Option<String>, // None = updated successfully
+    #[serde(default)]
+    pub backed_up: bool, // A snapshot exists to roll this target back
+    #[serde(default)]
+    pub files: SyncStats,
+}
+
+/// File counts of one target sync
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncStats {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+impl std::fmt::Display for SyncStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "+{} ~{} -{} files", self.added, self.updated, self.removed)
+    }
+}
+
+/// One build run, persisted under the data directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildRecord {
+    pub id: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub started_at: chrono::DateTime<chrono::Local>,
+    pub finished_at: chrono::DateTime<chrono::Local>,
+    pub duration_ms: u64,
+    pub package_manager: PackageManager,
+    pub commands: Vec<String>,
+    pub status: BuildStatus,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    pub targets: Vec<TargetOutcome>,
+    pub log: Vec<BuildLogLine>,
+    #[serde(default)]
+    pub command_outcomes: Vec<CommandOutcome>, // One per command, in execution order
+    #[serde(default)]
+    pub timeout: Option<TimeoutHit>, // Set when the build was killed for running too long
+}
+
+/// The timeout that stopped a build
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeoutHit {
+    pub command: String, // The command that was running
+    pub limit_secs: u64,
+    pub whole_build: bool, // The build timeout rather than the command's own
+}
+
+impl std::fmt::Display for TimeoutHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.whole_build {
+            write!(f, "build timed out after {}s while running '{}'", self.limit_secs, self.command)
+        } else {
+            write!(f, "'{}' timed out after {}s", self.command, self.limit_secs)
+        }
+    }
+}
+
+/// How one build command ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandStatus {
+    Succeeded,
+    Failed,
+    Cancelled,
+    TimedOut,
+    NotRun, // An earlier command failed or the build was stopped first
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandOutcome {
+    pub command: String,
+    pub status: CommandStatus,
+    pub exit_code: Option<i32>,
+    #[serde(default)]
+    pub duration_ms: u64,
+}
+
+/// Snapshot of one target taken right before a build overwrote it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetBackup {
+    pub project_id: String,
+    pub build_id: String,
+    pub target_id: String,
+    pub target_path: String,
+    pub created_at: chrono::DateTime<chrono::Local>,
+    pub version: Option<String>,     // Target version before the update
+    pub entries: Vec<String>,        // Output destinations that existed
+    pub missing_entries: Vec<String>, // Output destinations that did not exist yet
+    pub had_package_json: bool,
+    #[serde(default)]
+    pub incremental: bool, // Only the files below were saved; older backups copied whole destinations
+    #[serde(default)]
+    pub saved_files: Vec<String>, // Files the build replaced or removed, e.g. "dist/index.js"
+    #[serde(default)]
+    pub added_files: Vec<String>, // Files the build created, removed again on rollback
+}
+
+/// Layout of projects.json; older layouts are migrated on load
+/// v1 was a bare array of projects, v2 wraps it with a schema version,
+/// v3 replaces the selected script names with typed build steps
+pub const PROJECT_STORE_VERSION: u32 = 3;
+
+/// The versioned envelope projects.json is stored in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStore {
+    pub version: u32,
+    pub projects: Vec<Project>,
+}
+
+/// Portable set of project definitions written by export and read by import
+/// Shaped like the store itself, so a projects.json can be imported as well
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectBundle {
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Local>,
+    pub projects: Vec<Project>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    Json,
+    Toml,
+}
+
+/// What import does with a project whose ID or name is already taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    #[default]
+    Skip,
+    Overwrite, // Replace the stored project, keeping its ID
+    Rename,    // Import under a new ID and a free name, e.g. "ui-kit (2)"
+}
+
+impl ConflictPolicy {
+    pub const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Skip, ConflictPolicy::Overwrite, ConflictPolicy::Rename];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Rename => "rename",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ConflictPolicy> {
+        ConflictPolicy::ALL.iter().copied().find(|p| p.as_str() == name.trim().to_lowercase())
+    }
+}
+
+impl std::fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Replaces a path prefix on import, e.g. /Users/alice -> /home/bob
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathRemap {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    Added,
+    Skipped,
+    Overwritten,
+    Renamed,
+}
+
+/// One bundled project and what import did with it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportOutcome {
+    pub project: Project, // As imported: remapped, and renamed if it had to be
+    pub action: ImportAction,
+}
+
+/// Application-wide settings stored next to the project list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub backup_retention: usize, // Builds per project whose target snapshots are kept
+    pub consumer_search_roots: Vec<String>, // Folders "Find consumers" scans; empty = the project's parent folder
+    pub active_profile: String, // Profile the GUI, and CLI runs without --profile, use
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            backup_retention: 5,
+            consumer_search_roots: Vec::new(),
+            active_profile: "default".to_string(),
+        }
+    }
+}
+
+/// Where the data folder in use was configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataDirSource {
+    Flag,        // --config-dir
+    Environment, // LIBRARY_BUILD_MANAGEMENT_DIR
+    Settings,    // Chosen in the Settings page
+    Default,     // ~/.library-build-management
+}
+
+impl std::fmt::Display for DataDirSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match self {
+            DataDirSource::Flag => "--config-dir",
+            DataDirSource::Environment => "LIBRARY_BUILD_MANAGEMENT_DIR",
+            DataDirSource::Settings => "Settings",
+            DataDirSource::Default => "default",
+        };
+        write!(f, "{}", source)
+    }
+}
+
+/// An installed copy of a project's package found in some consumer's node_modules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsumerCandidate {
+    pub path: String,            // The node_modules/<package> folder, usable as a target
+    pub consumer_path: String,   // The folder whose node_modules holds it
+    pub installed_version: Option<String>,
+    pub is_target: bool,         // Already one of the project's targets
+}
+
+/// How a file in a target would change when the build output is copied in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub path: String, // Relative to the target root
+    pub kind: FileChangeKind,
+}
+
+/// What a build would do to one target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetPlan {
+    pub target_id: String,
+    pub path: String,
+    pub version_bump: VersionBump,
+    pub current_version: Option<String>,
+    pub new_version: Option<String>,
+    pub file_changes: Vec<FileChange>,
+    pub error: Option<String>, // Set when the target cannot be updated as planned
+}
+
+/// Everything a build would do, resolved without running or writing anything
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildPlan {
+    pub project_id: String,
+    pub project_name: String,
+    pub package_manager: PackageManager,
+    pub commands: Vec<String>,      // Script names, in execution order
+    pub command_lines: Vec<String>, // What actually runs, e.g. "pnpm run build"
+    pub command_timeouts: Vec<Option<u64>>, // Seconds, one per step
+    pub build_timeout_secs: Option<u64>,
+    pub output_dirs: Vec<OutputDir>,
+    pub env: Vec<String>, // "KEY=value" for every variable set, secret values masked
+    pub targets: Vec<TargetPlan>,
+    pub warnings: Vec<String>,
 }